@@ -0,0 +1,160 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+impl std::fmt::Display for TouchPhase {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A platform-neutral input event. Positions are relative to the window's client area.
+///
+/// Key events compare like `KeyCode`, by virtual key only.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputEvent {
+    Key {
+        key: KeyCode,
+        state: KeyState,
+        repeat: bool,
+        modifiers: Modifiers,
+    },
+    Text(TextInputEvent),
+    MouseButton {
+        button: MouseButton,
+        state: ButtonState,
+        position: PhysicalPosition<i32>,
+        buttons: MouseButtons,
+        modifiers: Modifiers,
+    },
+    MouseMove {
+        position: PhysicalPosition<i32>,
+        buttons: MouseButtons,
+        modifiers: Modifiers,
+    },
+    MouseWheel {
+        delta: WheelDelta,
+        position: PhysicalPosition<i32>,
+        buttons: MouseButtons,
+        modifiers: Modifiers,
+    },
+    CursorEntered {
+        position: PhysicalPosition<i32>,
+    },
+    CursorLeft,
+    Touch {
+        point: TouchPoint,
+        phase: TouchPhase,
+    },
+    Focused,
+    Unfocused,
+}
+
+impl InputEvent {
+    /// Touch positions are rounded to whole pixels.
+    #[inline]
+    pub fn position(&self) -> Option<PhysicalPosition<i32>> {
+        match self {
+            Self::MouseButton { position, .. }
+            | Self::MouseMove { position, .. }
+            | Self::MouseWheel { position, .. }
+            | Self::CursorEntered { position } => Some(*position),
+            Self::Touch { point, .. } => Some(PhysicalPosition::new(
+                point.position.x.round() as i32,
+                point.position.y.round() as i32,
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl From<TextInputEvent> for InputEvent {
+    #[inline]
+    fn from(value: TextInputEvent) -> Self {
+        Self::Text(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_event_position() {
+        let position = PhysicalPosition::new(3, 4);
+        let event = InputEvent::MouseMove {
+            position,
+            buttons: MouseButtons::from([MouseButton::Left]),
+            modifiers: Modifiers::new(),
+        };
+        assert!(event.position() == Some(position));
+        assert!(InputEvent::Focused.position().is_none());
+        let event = InputEvent::from(TextInputEvent::Commit("a".to_string()));
+        assert!(event.position().is_none());
+        let key = |scan_code| InputEvent::Key {
+            key: KeyCode::new(VirtualKey::Shift, ScanCode(scan_code)),
+            state: KeyState::Pressed,
+            repeat: false,
+            modifiers: Modifiers::new(),
+        };
+        assert!(key(0x2a) == key(0x36));
+        let event = InputEvent::Touch {
+            point: TouchPoint::new(PointerId(1), PointerKind::Touch, (1.4, 2.6)),
+            phase: TouchPhase::Started,
+        };
+        assert!(event.position() == Some(PhysicalPosition::new(1, 3)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn input_event_serde() {
+        let events = [
+            InputEvent::Key {
+                key: KeyCode::new(VirtualKey::A, ScanCode(0x1e)),
+                state: KeyState::Pressed,
+                repeat: true,
+                modifiers: Modifier::LCtrl.into(),
+            },
+            InputEvent::MouseButton {
+                button: MouseButton::Ex(0),
+                state: ButtonState::Released,
+                position: PhysicalPosition::new(10, 20),
+                buttons: MouseButton::Left | MouseButton::Right,
+                modifiers: Modifier::Shift.into(),
+            },
+            InputEvent::MouseWheel {
+                delta: WheelDelta::from_raw(MouseWheelAxis::Horizontal, -120),
+                position: PhysicalPosition::new(0, 0),
+                buttons: MouseButtons::new(),
+                modifiers: Modifier::LCtrl | Modifier::LShift,
+            },
+            InputEvent::Touch {
+                point: TouchPoint {
+                    pressure: Some(0.5),
+                    contact_size: Some(PhysicalSize::new(4.0, 6.0)),
+                    ..TouchPoint::new(PointerId(7), PointerKind::Touch, (-1.0, 5.5))
+                },
+                phase: TouchPhase::Moved,
+            },
+            InputEvent::CursorLeft,
+        ];
+        for event in events {
+            let json = serde_json::to_string(&event).unwrap();
+            let de = serde_json::from_str::<InputEvent>(&json).unwrap();
+            assert!(de == event);
+            if let (InputEvent::Key { key: a, .. }, InputEvent::Key { key: b, .. }) = (&de, &event)
+            {
+                assert!(a.scan_code == b.scan_code);
+            }
+        }
+    }
+}
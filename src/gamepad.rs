@@ -0,0 +1,377 @@
+use super::*;
+
+mod mapping;
+
+pub use mapping::*;
+
+/// Buttons of the standard gamepad layout, named by position on an Xbox style controller.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    LeftBumper,
+    RightBumper,
+    /// Pressed while `GamepadAxis::LeftTrigger` is past `GamepadConfig::trigger_threshold`.
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Guide,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const ALL: [Self; 17] = [
+        Self::A,
+        Self::B,
+        Self::X,
+        Self::Y,
+        Self::LeftBumper,
+        Self::RightBumper,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+        Self::Select,
+        Self::Start,
+        Self::Guide,
+        Self::LeftStick,
+        Self::RightStick,
+        Self::DPadUp,
+        Self::DPadDown,
+        Self::DPadLeft,
+        Self::DPadRight,
+    ];
+
+    #[inline]
+    fn as_u32(&self) -> u32 {
+        0x01 << (*self as u32)
+    }
+}
+
+impl std::fmt::Display for GamepadButton {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// `LeftX` and `RightX` are positive to the right, `LeftY` and `RightY` positive downward,
+/// and triggers are in `0.0..=1.0`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    pub const ALL: [Self; 6] = [
+        Self::LeftX,
+        Self::LeftY,
+        Self::RightX,
+        Self::RightY,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+    ];
+
+    #[inline]
+    pub fn is_trigger(&self) -> bool {
+        matches!(self, Self::LeftTrigger | Self::RightTrigger)
+    }
+
+    #[inline]
+    fn trigger_button(&self) -> Option<GamepadButton> {
+        match self {
+            Self::LeftTrigger => Some(GamepadButton::LeftTrigger),
+            Self::RightTrigger => Some(GamepadButton::RightTrigger),
+            _ => None,
+        }
+    }
+
+    /// The other axis of the same stick.
+    #[inline]
+    fn pair(&self) -> Option<Self> {
+        match self {
+            Self::LeftX => Some(Self::LeftY),
+            Self::LeftY => Some(Self::LeftX),
+            Self::RightX => Some(Self::RightY),
+            Self::RightY => Some(Self::RightX),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GamepadAxis {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    Squared,
+    Cubed,
+    /// Raises the magnitude to the given power.
+    Power(f32),
+}
+
+impl ResponseCurve {
+    /// Applies the curve to `value` in `-1.0..=1.0`, keeping the sign.
+    #[inline]
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        let magnitude = match self {
+            Self::Linear => magnitude,
+            Self::Squared => magnitude * magnitude,
+            Self::Cubed => magnitude * magnitude * magnitude,
+            Self::Power(p) => magnitude.powf(*p),
+        };
+        magnitude.copysign(value)
+    }
+}
+
+/// Values inside `deadzone` become 0 and the rest is rescaled to start from 0 before `curve`
+/// is applied. `deadzone` is clamped to `0.0..=1.0`, where `1.0` makes the whole range dead.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxisConfig {
+    pub deadzone: f32,
+    pub curve: ResponseCurve,
+}
+
+impl AxisConfig {
+    #[inline]
+    pub fn new(deadzone: f32, curve: ResponseCurve) -> Self {
+        Self { deadzone, curve }
+    }
+
+    #[inline]
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(-1.0, 1.0);
+        self.scale(value.abs()).copysign(value)
+    }
+
+    /// Applies the deadzone to the length of `(x, y)` so that diagonals are not cut off.
+    pub fn apply_stick(&self, x: f32, y: f32) -> (f32, f32) {
+        let length = x.hypot(y);
+        if length == 0.0 {
+            return (0.0, 0.0);
+        }
+        let scale = self.scale(length.min(1.0)) / length;
+        (x * scale, y * scale)
+    }
+
+    #[inline]
+    fn scale(&self, magnitude: f32) -> f32 {
+        let deadzone = if self.deadzone.is_nan() {
+            0.0
+        } else {
+            self.deadzone.clamp(0.0, 1.0)
+        };
+        if magnitude <= deadzone {
+            return 0.0;
+        }
+        self.curve.apply((magnitude - deadzone) / (1.0 - deadzone))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadConfig {
+    pub stick: AxisConfig,
+    pub trigger: AxisConfig,
+    /// The processed trigger value at which `LeftTrigger` and `RightTrigger` are pressed.
+    pub trigger_threshold: f32,
+}
+
+impl Default for GamepadConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stick: AxisConfig::new(0.2, ResponseCurve::Linear),
+            trigger: AxisConfig::new(0.1, ResponseCurve::Linear),
+            trigger_threshold: 0.5,
+        }
+    }
+}
+
+/// Tracks the buttons and axes of a gamepad and reports button edges.
+#[derive(Clone, Debug)]
+pub struct GamepadState {
+    config: GamepadConfig,
+    buttons: u32,
+    axes: [f32; 6],
+}
+
+impl GamepadState {
+    #[inline]
+    pub fn new(config: GamepadConfig) -> Self {
+        Self {
+            config,
+            buttons: 0,
+            axes: [0.0; 6],
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &GamepadConfig {
+        &self.config
+    }
+
+    #[inline]
+    pub fn set_config(&mut self, config: GamepadConfig) {
+        self.config = config;
+    }
+
+    /// Returns the new state if the button changed.
+    pub fn set_button(&mut self, button: GamepadButton, state: ButtonState) -> Option<ButtonState> {
+        let pressed = state == ButtonState::Pressed;
+        if self.is_pressed(button) == pressed {
+            return None;
+        }
+        self.buttons ^= button.as_u32();
+        Some(state)
+    }
+
+    /// Sets the raw value of `axis`. Returns an edge when a trigger crosses the threshold.
+    pub fn set_axis(
+        &mut self,
+        axis: GamepadAxis,
+        value: f32,
+    ) -> Option<(GamepadButton, ButtonState)> {
+        self.axes[axis as usize] = value;
+        let button = axis.trigger_button()?;
+        let state = if self.axis(axis) >= self.config.trigger_threshold {
+            ButtonState::Pressed
+        } else {
+            ButtonState::Released
+        };
+        self.set_button(button, state).map(|state| (button, state))
+    }
+
+    #[inline]
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons & button.as_u32() != 0
+    }
+
+    #[inline]
+    pub fn pressed(&self) -> Vec<GamepadButton> {
+        GamepadButton::ALL
+            .into_iter()
+            .filter(|b| self.is_pressed(*b))
+            .collect()
+    }
+
+    #[inline]
+    pub fn raw_axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+
+    /// The value of `axis` after the deadzone and response curve.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        let value = self.raw_axis(axis);
+        match axis.pair() {
+            Some(pair) => {
+                let (v, _) = self.config.stick.apply_stick(value, self.raw_axis(pair));
+                v
+            }
+            None => self.config.trigger.apply(value).max(0.0),
+        }
+    }
+
+    /// Releases every button and centers the axes, e.g. when the gamepad is disconnected.
+    pub fn release_all(&mut self) -> Vec<GamepadButton> {
+        let pressed = self.pressed();
+        self.buttons = 0;
+        self.axes = [0.0; 6];
+        pressed
+    }
+}
+
+impl Default for GamepadState {
+    #[inline]
+    fn default() -> Self {
+        Self::new(GamepadConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_config() {
+        let config = AxisConfig::new(0.2, ResponseCurve::Linear);
+        assert!(config.apply(0.1) == 0.0);
+        assert!(config.apply(-0.2) == 0.0);
+        assert!((config.apply(0.6) - 0.5).abs() < 1e-6);
+        assert!(config.apply(-1.5) == -1.0);
+        let config = AxisConfig::new(0.0, ResponseCurve::Squared);
+        assert!(config.apply(-0.5) == -0.25);
+        assert!(ResponseCurve::Power(3.0).apply(0.5) == ResponseCurve::Cubed.apply(0.5));
+        let config = AxisConfig::new(0.2, ResponseCurve::Linear);
+        let (x, y) = config.apply_stick(0.15, 0.15);
+        assert!(x > 0.0 && y > 0.0);
+        assert!(config.apply_stick(0.1, 0.1) == (0.0, 0.0));
+    }
+
+    #[test]
+    fn axis_config_deadzone_out_of_range() {
+        for deadzone in [1.0, 1.5, f32::INFINITY] {
+            let config = AxisConfig::new(deadzone, ResponseCurve::Linear);
+            assert!(config.apply(1.0) == 0.0);
+            assert!(config.apply_stick(1.0, 0.0) == (0.0, 0.0));
+        }
+        for deadzone in [-0.5, f32::NAN] {
+            let config = AxisConfig::new(deadzone, ResponseCurve::Linear);
+            assert!(config.apply(0.0) == 0.0);
+            assert!(config.apply(-0.5) == -0.5);
+        }
+    }
+
+    #[test]
+    fn gamepad_state() {
+        let mut state = GamepadState::default();
+        assert!(
+            state.set_button(GamepadButton::A, ButtonState::Pressed) == Some(ButtonState::Pressed)
+        );
+        assert!(
+            state
+                .set_button(GamepadButton::A, ButtonState::Pressed)
+                .is_none()
+        );
+        assert!(state.is_pressed(GamepadButton::A));
+        assert!(state.set_axis(GamepadAxis::LeftTrigger, 0.3).is_none());
+        assert!(
+            state.set_axis(GamepadAxis::LeftTrigger, 0.9)
+                == Some((GamepadButton::LeftTrigger, ButtonState::Pressed))
+        );
+        assert!(state.set_axis(GamepadAxis::LeftX, 0.1).is_none());
+        assert!(state.axis(GamepadAxis::LeftX) == 0.0);
+        state.set_axis(GamepadAxis::LeftX, 1.0);
+        assert!(state.axis(GamepadAxis::LeftX) == 1.0);
+        assert!(state.raw_axis(GamepadAxis::LeftX) == 1.0);
+        assert!(
+            state.set_axis(GamepadAxis::LeftTrigger, 0.0)
+                == Some((GamepadButton::LeftTrigger, ButtonState::Released))
+        );
+        assert!(state.release_all() == [GamepadButton::A]);
+        assert!(state.pressed().is_empty());
+        assert!(state.raw_axis(GamepadAxis::LeftX) == 0.0);
+    }
+}
@@ -0,0 +1,493 @@
+use super::*;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseMappingError {
+    InvalidGuid(String),
+    MissingName,
+    InvalidBinding(String),
+}
+
+impl std::fmt::Display for ParseMappingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidGuid(s) => write!(f, "invalid guid: {}", s),
+            Self::MissingName => write!(f, "missing name"),
+            Self::InvalidBinding(s) => write!(f, "invalid binding: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseMappingError {}
+
+/// The part of an axis an input or target covers, written as a `+` or `-` prefix.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisRange {
+    #[default]
+    Full,
+    Positive,
+    Negative,
+}
+
+impl AxisRange {
+    fn prefix(&self) -> &'static str {
+        match self {
+            Self::Full => "",
+            Self::Positive => "+",
+            Self::Negative => "-",
+        }
+    }
+
+    fn strip(s: &str) -> (Self, &str) {
+        if let Some(s) = s.strip_prefix('+') {
+            (Self::Positive, s)
+        } else if let Some(s) = s.strip_prefix('-') {
+            (Self::Negative, s)
+        } else {
+            (Self::Full, s)
+        }
+    }
+}
+
+/// A control of the device as numbered by the driver.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MappingInput {
+    /// `b0`
+    Button(u32),
+    /// `h0.4`. `mask` is 1 for up, 2 for right, 4 for down and 8 for left.
+    Hat { index: u32, mask: u8 },
+    /// `a0`, `+a0`, `-a0` and `a0~` for an inverted axis.
+    Axis {
+        index: u32,
+        range: AxisRange,
+        inverted: bool,
+    },
+}
+
+impl std::fmt::Display for MappingInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Button(index) => write!(f, "b{}", index),
+            Self::Hat { index, mask } => write!(f, "h{}.{}", index, mask),
+            Self::Axis {
+                index,
+                range,
+                inverted,
+            } => write!(
+                f,
+                "{}a{}{}",
+                range.prefix(),
+                index,
+                if *inverted { "~" } else { "" }
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for MappingInput {
+    type Err = ParseMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseMappingError::InvalidBinding(s.to_string());
+        let (range, rest) = AxisRange::strip(s);
+        if let Some(rest) = rest.strip_prefix('a') {
+            let (rest, inverted) = match rest.strip_suffix('~') {
+                Some(rest) => (rest, true),
+                None => (rest, false),
+            };
+            return Ok(Self::Axis {
+                index: rest.parse().map_err(|_| err())?,
+                range,
+                inverted,
+            });
+        }
+        if range != AxisRange::Full {
+            return Err(err());
+        }
+        if let Some(rest) = s.strip_prefix('b') {
+            Ok(Self::Button(rest.parse().map_err(|_| err())?))
+        } else if let Some(rest) = s.strip_prefix('h') {
+            let (index, mask) = rest.split_once('.').ok_or_else(err)?;
+            Ok(Self::Hat {
+                index: index.parse().map_err(|_| err())?,
+                mask: mask.parse().map_err(|_| err())?,
+            })
+        } else {
+            Err(err())
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MappingTarget {
+    Button(GamepadButton),
+    Axis { axis: GamepadAxis, range: AxisRange },
+}
+
+const BUTTON_NAMES: [(GamepadButton, &str); 15] = [
+    (GamepadButton::A, "a"),
+    (GamepadButton::B, "b"),
+    (GamepadButton::X, "x"),
+    (GamepadButton::Y, "y"),
+    (GamepadButton::LeftBumper, "leftshoulder"),
+    (GamepadButton::RightBumper, "rightshoulder"),
+    (GamepadButton::Select, "back"),
+    (GamepadButton::Start, "start"),
+    (GamepadButton::Guide, "guide"),
+    (GamepadButton::LeftStick, "leftstick"),
+    (GamepadButton::RightStick, "rightstick"),
+    (GamepadButton::DPadUp, "dpup"),
+    (GamepadButton::DPadDown, "dpdown"),
+    (GamepadButton::DPadLeft, "dpleft"),
+    (GamepadButton::DPadRight, "dpright"),
+];
+
+const AXIS_NAMES: [(GamepadAxis, &str); 6] = [
+    (GamepadAxis::LeftX, "leftx"),
+    (GamepadAxis::LeftY, "lefty"),
+    (GamepadAxis::RightX, "rightx"),
+    (GamepadAxis::RightY, "righty"),
+    (GamepadAxis::LeftTrigger, "lefttrigger"),
+    (GamepadAxis::RightTrigger, "righttrigger"),
+];
+
+impl std::fmt::Display for MappingTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // SDL has no trigger buttons, so they are written as the trigger axes.
+            Self::Button(GamepadButton::LeftTrigger) => write!(f, "lefttrigger"),
+            Self::Button(GamepadButton::RightTrigger) => write!(f, "righttrigger"),
+            Self::Button(button) => {
+                let (_, name) = BUTTON_NAMES.iter().find(|(b, _)| b == button).unwrap();
+                write!(f, "{}", name)
+            }
+            Self::Axis { axis, range } => {
+                let (_, name) = AXIS_NAMES.iter().find(|(a, _)| a == axis).unwrap();
+                write!(f, "{}{}", range.prefix(), name)
+            }
+        }
+    }
+}
+
+impl MappingTarget {
+    /// Returns `Ok(None)` for names of controls that gamepad does not model, such as `paddle1`.
+    fn parse(s: &str) -> Result<Option<Self>, ParseMappingError> {
+        let (range, name) = AxisRange::strip(s);
+        if let Some((axis, _)) = AXIS_NAMES.iter().find(|(_, n)| *n == name) {
+            return Ok(Some(Self::Axis { axis: *axis, range }));
+        }
+        if let Some((button, _)) = BUTTON_NAMES.iter().find(|(_, n)| *n == name) {
+            if range != AxisRange::Full {
+                return Err(ParseMappingError::InvalidBinding(s.to_string()));
+            }
+            return Ok(Some(Self::Button(*button)));
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MappingBinding {
+    pub target: MappingTarget,
+    pub input: MappingInput,
+}
+
+/// The raw state of a device before mapping. Axes are in `-1.0..=1.0` and hats are masks of
+/// `MappingInput::Hat` directions.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RawGamepad {
+    pub buttons: Vec<bool>,
+    pub axes: Vec<f32>,
+    pub hats: Vec<u8>,
+}
+
+impl RawGamepad {
+    /// Buttons and hats give `0.0` or `1.0`, half axes give `0.0..=1.0` and full axes
+    /// `-1.0..=1.0`. Missing controls give `None`.
+    fn value(&self, input: MappingInput) -> Option<f32> {
+        let value = match input {
+            MappingInput::Button(index) => {
+                let pressed = *self.buttons.get(index as usize)?;
+                if pressed { 1.0 } else { 0.0 }
+            }
+            MappingInput::Hat { index, mask } => {
+                let hat = *self.hats.get(index as usize)?;
+                if hat & mask != 0 { 1.0 } else { 0.0 }
+            }
+            MappingInput::Axis {
+                index,
+                range,
+                inverted,
+            } => {
+                let v = *self.axes.get(index as usize)?;
+                let v = if inverted { -v } else { v };
+                match range {
+                    AxisRange::Full => v,
+                    AxisRange::Positive => v.max(0.0),
+                    AxisRange::Negative => (-v).max(0.0),
+                }
+            }
+        };
+        Some(value)
+    }
+}
+
+/// A mapping in the SDL GameControllerDB format such as
+/// `030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,b:b1,leftx:a0,platform:Linux,`.
+///
+/// `guid` is 32 hex digits, or `xinput` for the generic XInput mapping built into SDL.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadMapping {
+    pub guid: String,
+    pub name: String,
+    pub platform: Option<String>,
+    pub bindings: Vec<MappingBinding>,
+}
+
+impl GamepadMapping {
+    /// Parses each mapping line of a database file, skipping blank lines and `#` comments.
+    pub fn parse_db(db: &str) -> impl Iterator<Item = Result<Self, ParseMappingError>> + '_ {
+        db.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.parse())
+    }
+
+    #[inline]
+    pub fn input(&self, target: MappingTarget) -> Option<MappingInput> {
+        self.bindings
+            .iter()
+            .find(|b| b.target == target)
+            .map(|b| b.input)
+    }
+
+    /// Updates `state` from the raw device state and returns the button edges.
+    pub fn apply(
+        &self,
+        raw: &RawGamepad,
+        state: &mut GamepadState,
+    ) -> Vec<(GamepadButton, ButtonState)> {
+        let mut buttons = 0u32;
+        let mut mapped = 0u32;
+        let mut axes = [None; 6];
+        for binding in &self.bindings {
+            let full = matches!(
+                binding.input,
+                MappingInput::Axis {
+                    range: AxisRange::Full,
+                    ..
+                }
+            );
+            let trigger = full
+                && matches!(
+                    binding.target,
+                    MappingTarget::Axis { axis, range: AxisRange::Full } if axis.is_trigger()
+                );
+            // A full range trigger axis rests at -1.0, so a missing one must not read as half way.
+            let value = raw
+                .value(binding.input)
+                .unwrap_or(if trigger { -1.0 } else { 0.0 });
+            match binding.target {
+                MappingTarget::Button(button) => {
+                    mapped |= button.as_u32();
+                    if value > if full { 0.0 } else { 0.5 } {
+                        buttons |= button.as_u32();
+                    }
+                }
+                MappingTarget::Axis { axis, range } => {
+                    let magnitude = if full { (value + 1.0) / 2.0 } else { value };
+                    let value = match range {
+                        AxisRange::Full if trigger => magnitude,
+                        AxisRange::Full => value,
+                        AxisRange::Positive => magnitude,
+                        AxisRange::Negative => -magnitude,
+                    };
+                    *axes[axis as usize].get_or_insert(0.0) += value;
+                }
+            }
+        }
+        let mut edges = vec![];
+        for button in GamepadButton::ALL {
+            if mapped & button.as_u32() == 0 {
+                continue;
+            }
+            let s = if buttons & button.as_u32() != 0 {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            };
+            if let Some(s) = state.set_button(button, s) {
+                edges.push((button, s));
+            }
+        }
+        for axis in GamepadAxis::ALL {
+            if let Some(value) = axes[axis as usize]
+                && let Some(edge) = state.set_axis(axis, value.clamp(-1.0, 1.0))
+            {
+                edges.push(edge);
+            }
+        }
+        edges
+    }
+}
+
+impl std::fmt::Display for GamepadMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},", self.guid, self.name)?;
+        for binding in &self.bindings {
+            write!(f, "{}:{},", binding.target, binding.input)?;
+        }
+        if let Some(platform) = &self.platform {
+            write!(f, "platform:{},", platform)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for GamepadMapping {
+    type Err = ParseMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.trim().split(',');
+        let guid = fields.next().unwrap_or_default();
+        if guid != "xinput" && (guid.len() != 32 || !guid.chars().all(|c| c.is_ascii_hexdigit())) {
+            return Err(ParseMappingError::InvalidGuid(guid.to_string()));
+        }
+        let name = fields
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or(ParseMappingError::MissingName)?;
+        let mut platform = None;
+        let mut bindings = vec![];
+        for field in fields.filter(|f| !f.is_empty()) {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| ParseMappingError::InvalidBinding(field.to_string()))?;
+            match key {
+                "platform" => platform = Some(value.to_string()),
+                // Metadata for SDL itself.
+                "crc" | "hint" | "sdk>=" | "sdk<=" => {}
+                _ => {
+                    if let Some(target) = MappingTarget::parse(key)? {
+                        bindings.push(MappingBinding {
+                            target,
+                            input: value.parse()?,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            guid: guid.to_string(),
+            name: name.to_string(),
+            platform,
+            bindings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XBOX: &str = "030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,b:b1,\
+        back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,\
+        leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,\
+        righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,misc1:b11,platform:Linux,";
+
+    #[test]
+    fn parse_mapping() {
+        let mapping: GamepadMapping = XBOX.parse().unwrap();
+        assert!(mapping.name == "Xbox 360 Controller");
+        assert!(mapping.platform.as_deref() == Some("Linux"));
+        assert!(mapping.bindings.len() == 21);
+        assert!(
+            mapping.input(MappingTarget::Button(GamepadButton::DPadLeft))
+                == Some(MappingInput::Hat { index: 0, mask: 8 })
+        );
+        assert!(mapping.to_string() == XBOX.replace(",misc1:b11", ""));
+        let mapping: GamepadMapping = "03000000000000000000000000000000,Pad,+leftx:b1,-lefty:-a1~,"
+            .parse()
+            .unwrap();
+        assert!(
+            mapping.bindings[1]
+                == MappingBinding {
+                    target: MappingTarget::Axis {
+                        axis: GamepadAxis::LeftY,
+                        range: AxisRange::Negative,
+                    },
+                    input: MappingInput::Axis {
+                        index: 1,
+                        range: AxisRange::Negative,
+                        inverted: true,
+                    },
+                }
+        );
+        assert!(
+            "xyz,Pad,".parse::<GamepadMapping>()
+                == Err(ParseMappingError::InvalidGuid("xyz".to_string()))
+        );
+        assert!(
+            "03000000000000000000000000000000,,a:b0".parse::<GamepadMapping>()
+                == Err(ParseMappingError::MissingName)
+        );
+        assert!(
+            "03000000000000000000000000000000,Pad,a:q0".parse::<GamepadMapping>()
+                == Err(ParseMappingError::InvalidBinding("q0".to_string()))
+        );
+        let mapping: GamepadMapping = "xinput,XInput Controller,a:b0,b:b1,".parse().unwrap();
+        assert!(mapping.guid == "xinput");
+        assert!(mapping.to_string() == "xinput,XInput Controller,a:b0,b:b1,");
+        let db = format!("# comment\n\n{}\n", XBOX);
+        assert!(GamepadMapping::parse_db(&db).count() == 1);
+    }
+
+    #[test]
+    fn apply_mapping() {
+        let mapping: GamepadMapping = XBOX.parse().unwrap();
+        let mut state = GamepadState::default();
+        let mut raw = RawGamepad {
+            buttons: vec![false; 11],
+            axes: vec![0.0, 0.0, -1.0, 0.0, 0.0, -1.0],
+            hats: vec![0],
+        };
+        raw.buttons[0] = true;
+        raw.hats[0] = 0x02;
+        raw.axes[2] = 1.0;
+        raw.axes[0] = -1.0;
+        let edges = mapping.apply(&raw, &mut state);
+        assert!(
+            edges
+                == [
+                    (GamepadButton::A, ButtonState::Pressed),
+                    (GamepadButton::DPadRight, ButtonState::Pressed),
+                    (GamepadButton::LeftTrigger, ButtonState::Pressed),
+                ]
+        );
+        assert!(state.axis(GamepadAxis::LeftX) == -1.0);
+        assert!(state.axis(GamepadAxis::LeftTrigger) == 1.0);
+        assert!(state.axis(GamepadAxis::RightTrigger) == 0.0);
+        raw.buttons[0] = false;
+        let edges = mapping.apply(&raw, &mut state);
+        assert!(edges == [(GamepadButton::A, ButtonState::Released)]);
+    }
+
+    #[test]
+    fn apply_mapping_missing_trigger() {
+        let mapping: GamepadMapping = XBOX.parse().unwrap();
+        let mut state = GamepadState::default();
+        let raw = RawGamepad {
+            buttons: vec![false; 11],
+            axes: vec![0.0; 2],
+            hats: vec![0],
+        };
+        assert!(mapping.apply(&raw, &mut state).is_empty());
+        assert!(state.raw_axis(GamepadAxis::LeftTrigger) == 0.0);
+        assert!(state.raw_axis(GamepadAxis::RightTrigger) == 0.0);
+        assert!(state.axis(GamepadAxis::LeftX) == 0.0);
+    }
+}
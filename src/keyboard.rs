@@ -1,6 +1,30 @@
 #[cfg(windows)]
 use windows::Win32::UI::Input::KeyboardAndMouse::*;
 
+mod chord;
+mod dom;
+mod evdev;
+mod hid;
+mod labels;
+mod layout;
+mod macos;
+mod modifiers;
+mod names;
+mod shortcut;
+mod state;
+#[cfg(feature = "x11")]
+mod x11;
+
+pub use chord::*;
+pub use hid::*;
+pub use labels::*;
+pub use layout::*;
+pub use macos::*;
+pub use modifiers::*;
+pub use names::*;
+pub use shortcut::*;
+pub use state::*;
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyState {
@@ -24,6 +48,15 @@ pub enum VirtualKey {
     Shift,
     Ctrl,
     Alt,
+    LShift,
+    RShift,
+    LCtrl,
+    RCtrl,
+    LAlt,
+    RAlt,
+    LMeta,
+    RMeta,
+    ContextMenu,
     BackSpace,
     Enter,
     Space,
@@ -76,6 +109,19 @@ pub enum VirtualKey {
     X,
     Y,
     Z,
+    Semicolon,
+    Equal,
+    Comma,
+    Minus,
+    Period,
+    Slash,
+    Backquote,
+    LBracket,
+    Backslash,
+    RBracket,
+    Quote,
+    /// The extra key next to the left Shift on ISO keyboards.
+    IntlBackslash,
     F1,
     F2,
     F3,
@@ -116,16 +162,34 @@ pub enum VirtualKey {
     NumMul,
     NumDiv,
     NumDecimal,
+    NumEnter,
+    VolumeMute,
+    VolumeDown,
+    VolumeUp,
+    MediaNextTrack,
+    MediaPrevTrack,
+    MediaStop,
+    MediaPlayPause,
+    BrowserBack,
+    BrowserForward,
+    BrowserRefresh,
+    BrowserStop,
+    BrowserSearch,
+    BrowserFavorites,
+    BrowserHome,
+    LaunchMail,
+    LaunchMediaSelect,
+    LaunchApp1,
+    LaunchApp2,
+    /// A Windows virtual-key code without a named variant. Keys from other platforms that
+    /// have no variant are `Other(0)`.
     Other(u32),
 }
 
-impl std::fmt::Display for VirtualKey {
-    #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
+/// A PC/AT scan code set 1 make code.
+///
+/// Extended keys carry their prefix in the high byte (e.g. `0xE01D` for the right Ctrl key),
+/// the same form as `MapVirtualKeyW` with `MAPVK_VK_TO_VSC_EX`. `ScanCode(0)` means no scan code.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScanCode(pub u32);
@@ -138,6 +202,7 @@ impl std::fmt::Display for ScanCode {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyCode {
     pub vkey: VirtualKey,
     pub scan_code: ScanCode,
@@ -148,6 +213,32 @@ impl KeyCode {
     pub fn new(vkey: VirtualKey, scan_code: ScanCode) -> Self {
         Self { vkey, scan_code }
     }
+
+    /// Builds a `KeyCode` from the virtual key and `LPARAM` of a Windows `WM_KEYDOWN`, `WM_KEYUP`,
+    /// `WM_SYSKEYDOWN` or `WM_SYSKEYUP` message.
+    ///
+    /// The scan code is bits 16 to 23 of `lparam`, with the `0xE0` prefix when the extended-key
+    /// bit 24 is set. `Shift`, `Ctrl` and `Alt` are resolved to their left or right key and `Enter`
+    /// to `NumEnter` from the scan code, since the message only carries the generic virtual key.
+    pub fn from_key_message(vkey: VirtualKey, lparam: isize) -> Self {
+        let scan_code = ((lparam >> 16) & 0xFF) as u32;
+        let scan_code = if lparam & (0x01 << 24) != 0 {
+            ScanCode(0xE000 | scan_code)
+        } else {
+            ScanCode(scan_code)
+        };
+        let vkey = match (vkey, scan_code.0) {
+            (VirtualKey::Shift, 0x2A) => VirtualKey::LShift,
+            (VirtualKey::Shift, 0x36) => VirtualKey::RShift,
+            (VirtualKey::Ctrl, 0x1D) => VirtualKey::LCtrl,
+            (VirtualKey::Ctrl, 0xE01D) => VirtualKey::RCtrl,
+            (VirtualKey::Alt, 0x38) => VirtualKey::LAlt,
+            (VirtualKey::Alt, 0xE038) => VirtualKey::RAlt,
+            (VirtualKey::Enter, 0xE01C) => VirtualKey::NumEnter,
+            _ => vkey,
+        };
+        Self { vkey, scan_code }
+    }
 }
 
 impl PartialEq for KeyCode {
@@ -199,6 +290,18 @@ impl From<VIRTUAL_KEY> for VirtualKey {
             VK_X => VirtualKey::X,
             VK_Y => VirtualKey::Y,
             VK_Z => VirtualKey::Z,
+            VK_OEM_1 => VirtualKey::Semicolon,
+            VK_OEM_PLUS => VirtualKey::Equal,
+            VK_OEM_COMMA => VirtualKey::Comma,
+            VK_OEM_MINUS => VirtualKey::Minus,
+            VK_OEM_PERIOD => VirtualKey::Period,
+            VK_OEM_2 => VirtualKey::Slash,
+            VK_OEM_3 => VirtualKey::Backquote,
+            VK_OEM_4 => VirtualKey::LBracket,
+            VK_OEM_5 => VirtualKey::Backslash,
+            VK_OEM_6 => VirtualKey::RBracket,
+            VK_OEM_7 => VirtualKey::Quote,
+            VK_OEM_102 => VirtualKey::IntlBackslash,
             VK_NUMPAD1 => VirtualKey::Num1,
             VK_NUMPAD2 => VirtualKey::Num2,
             VK_NUMPAD3 => VirtualKey::Num3,
@@ -215,6 +318,15 @@ impl From<VIRTUAL_KEY> for VirtualKey {
             VK_SHIFT => VirtualKey::Shift,
             VK_CONTROL => VirtualKey::Ctrl,
             VK_MENU => VirtualKey::Alt,
+            VK_LSHIFT => VirtualKey::LShift,
+            VK_RSHIFT => VirtualKey::RShift,
+            VK_LCONTROL => VirtualKey::LCtrl,
+            VK_RCONTROL => VirtualKey::RCtrl,
+            VK_LMENU => VirtualKey::LAlt,
+            VK_RMENU => VirtualKey::RAlt,
+            VK_LWIN => VirtualKey::LMeta,
+            VK_RWIN => VirtualKey::RMeta,
+            VK_APPS => VirtualKey::ContextMenu,
             VK_BACK => VirtualKey::BackSpace,
             VK_RETURN => VirtualKey::Enter,
             VK_SPACE => VirtualKey::Space,
@@ -261,6 +373,24 @@ impl From<VIRTUAL_KEY> for VirtualKey {
             VK_F22 => VirtualKey::F22,
             VK_F23 => VirtualKey::F23,
             VK_F24 => VirtualKey::F24,
+            VK_VOLUME_MUTE => VirtualKey::VolumeMute,
+            VK_VOLUME_DOWN => VirtualKey::VolumeDown,
+            VK_VOLUME_UP => VirtualKey::VolumeUp,
+            VK_MEDIA_NEXT_TRACK => VirtualKey::MediaNextTrack,
+            VK_MEDIA_PREV_TRACK => VirtualKey::MediaPrevTrack,
+            VK_MEDIA_STOP => VirtualKey::MediaStop,
+            VK_MEDIA_PLAY_PAUSE => VirtualKey::MediaPlayPause,
+            VK_BROWSER_BACK => VirtualKey::BrowserBack,
+            VK_BROWSER_FORWARD => VirtualKey::BrowserForward,
+            VK_BROWSER_REFRESH => VirtualKey::BrowserRefresh,
+            VK_BROWSER_STOP => VirtualKey::BrowserStop,
+            VK_BROWSER_SEARCH => VirtualKey::BrowserSearch,
+            VK_BROWSER_FAVORITES => VirtualKey::BrowserFavorites,
+            VK_BROWSER_HOME => VirtualKey::BrowserHome,
+            VK_LAUNCH_MAIL => VirtualKey::LaunchMail,
+            VK_LAUNCH_MEDIA_SELECT => VirtualKey::LaunchMediaSelect,
+            VK_LAUNCH_APP1 => VirtualKey::LaunchApp1,
+            VK_LAUNCH_APP2 => VirtualKey::LaunchApp2,
             _ => VirtualKey::Other(value.0 as _),
         }
     }
@@ -306,6 +436,18 @@ impl From<VirtualKey> for VIRTUAL_KEY {
             VirtualKey::X => VK_X,
             VirtualKey::Y => VK_Y,
             VirtualKey::Z => VK_Z,
+            VirtualKey::Semicolon => VK_OEM_1,
+            VirtualKey::Equal => VK_OEM_PLUS,
+            VirtualKey::Comma => VK_OEM_COMMA,
+            VirtualKey::Minus => VK_OEM_MINUS,
+            VirtualKey::Period => VK_OEM_PERIOD,
+            VirtualKey::Slash => VK_OEM_2,
+            VirtualKey::Backquote => VK_OEM_3,
+            VirtualKey::LBracket => VK_OEM_4,
+            VirtualKey::Backslash => VK_OEM_5,
+            VirtualKey::RBracket => VK_OEM_6,
+            VirtualKey::Quote => VK_OEM_7,
+            VirtualKey::IntlBackslash => VK_OEM_102,
             VirtualKey::Num1 => VK_NUMPAD1,
             VirtualKey::Num2 => VK_NUMPAD2,
             VirtualKey::Num3 => VK_NUMPAD3,
@@ -322,6 +464,15 @@ impl From<VirtualKey> for VIRTUAL_KEY {
             VirtualKey::Shift => VK_SHIFT,
             VirtualKey::Ctrl => VK_CONTROL,
             VirtualKey::Alt => VK_MENU,
+            VirtualKey::LShift => VK_LSHIFT,
+            VirtualKey::RShift => VK_RSHIFT,
+            VirtualKey::LCtrl => VK_LCONTROL,
+            VirtualKey::RCtrl => VK_RCONTROL,
+            VirtualKey::LAlt => VK_LMENU,
+            VirtualKey::RAlt => VK_RMENU,
+            VirtualKey::LMeta => VK_LWIN,
+            VirtualKey::RMeta => VK_RWIN,
+            VirtualKey::ContextMenu => VK_APPS,
             VirtualKey::BackSpace => VK_BACK,
             VirtualKey::Enter => VK_RETURN,
             VirtualKey::Space => VK_SPACE,
@@ -344,6 +495,7 @@ impl From<VirtualKey> for VIRTUAL_KEY {
             VirtualKey::NumMul => VK_MULTIPLY,
             VirtualKey::NumDiv => VK_DIVIDE,
             VirtualKey::NumDecimal => VK_DECIMAL,
+            VirtualKey::NumEnter => VK_RETURN,
             VirtualKey::F1 => VK_F1,
             VirtualKey::F2 => VK_F2,
             VirtualKey::F3 => VK_F3,
@@ -368,17 +520,45 @@ impl From<VirtualKey> for VIRTUAL_KEY {
             VirtualKey::F22 => VK_F22,
             VirtualKey::F23 => VK_F23,
             VirtualKey::F24 => VK_F24,
+            VirtualKey::VolumeMute => VK_VOLUME_MUTE,
+            VirtualKey::VolumeDown => VK_VOLUME_DOWN,
+            VirtualKey::VolumeUp => VK_VOLUME_UP,
+            VirtualKey::MediaNextTrack => VK_MEDIA_NEXT_TRACK,
+            VirtualKey::MediaPrevTrack => VK_MEDIA_PREV_TRACK,
+            VirtualKey::MediaStop => VK_MEDIA_STOP,
+            VirtualKey::MediaPlayPause => VK_MEDIA_PLAY_PAUSE,
+            VirtualKey::BrowserBack => VK_BROWSER_BACK,
+            VirtualKey::BrowserForward => VK_BROWSER_FORWARD,
+            VirtualKey::BrowserRefresh => VK_BROWSER_REFRESH,
+            VirtualKey::BrowserStop => VK_BROWSER_STOP,
+            VirtualKey::BrowserSearch => VK_BROWSER_SEARCH,
+            VirtualKey::BrowserFavorites => VK_BROWSER_FAVORITES,
+            VirtualKey::BrowserHome => VK_BROWSER_HOME,
+            VirtualKey::LaunchMail => VK_LAUNCH_MAIL,
+            VirtualKey::LaunchMediaSelect => VK_LAUNCH_MEDIA_SELECT,
+            VirtualKey::LaunchApp1 => VK_LAUNCH_APP1,
+            VirtualKey::LaunchApp2 => VK_LAUNCH_APP2,
             VirtualKey::Other(v) => VIRTUAL_KEY(v as _),
         }
     }
 }
 
+// Windows has no virtual key for the numpad Enter key, only VK_RETURN with the extended flag.
+#[cfg(windows)]
+const NUM_ENTER_SCAN_CODE: u32 = 0xE01C;
+
 #[cfg(windows)]
 impl From<VirtualKey> for KeyCode {
     #[inline]
     fn from(vkey: VirtualKey) -> Self {
+        if vkey == VirtualKey::NumEnter {
+            return Self {
+                vkey,
+                scan_code: ScanCode(NUM_ENTER_SCAN_CODE),
+            };
+        }
         unsafe {
-            let scan_code = MapVirtualKeyW(VIRTUAL_KEY::from(vkey).0 as u32, MAPVK_VK_TO_VSC);
+            let scan_code = MapVirtualKeyW(VIRTUAL_KEY::from(vkey).0 as u32, MAPVK_VK_TO_VSC_EX);
             Self {
                 vkey,
                 scan_code: ScanCode(scan_code),
@@ -391,8 +571,14 @@ impl From<VirtualKey> for KeyCode {
 impl From<ScanCode> for KeyCode {
     #[inline]
     fn from(scan_code: ScanCode) -> Self {
+        if scan_code.0 == NUM_ENTER_SCAN_CODE {
+            return Self {
+                vkey: VirtualKey::NumEnter,
+                scan_code,
+            };
+        }
         unsafe {
-            let vkey = VIRTUAL_KEY(MapVirtualKeyW(scan_code.0, MAPVK_VSC_TO_VK) as u16);
+            let vkey = VIRTUAL_KEY(MapVirtualKeyW(scan_code.0, MAPVK_VSC_TO_VK_EX) as u16);
             Self {
                 vkey: vkey.into(),
                 scan_code,
@@ -400,3 +586,74 @@ impl From<ScanCode> for KeyCode {
         }
     }
 }
+
+#[cfg(not(windows))]
+impl From<VirtualKey> for KeyCode {
+    #[inline]
+    fn from(vkey: VirtualKey) -> Self {
+        let scan_code = vkey
+            .to_evdev()
+            .and_then(ScanCode::from_evdev)
+            .unwrap_or(ScanCode(0));
+        Self { vkey, scan_code }
+    }
+}
+
+#[cfg(not(windows))]
+impl From<ScanCode> for KeyCode {
+    #[inline]
+    fn from(scan_code: ScanCode) -> Self {
+        let vkey = scan_code
+            .to_evdev()
+            .map(VirtualKey::from_evdev)
+            .unwrap_or(VirtualKey::Other(0));
+        Self { vkey, scan_code }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_code_from_key_message() {
+        let key = KeyCode::from_key_message(VirtualKey::Shift, 0x00360001);
+        assert!(key.vkey == VirtualKey::RShift);
+        assert!(key.scan_code == ScanCode(0x36));
+        let key = KeyCode::from_key_message(VirtualKey::Ctrl, 0x011D0001);
+        assert!(key.vkey == VirtualKey::RCtrl);
+        assert!(key.scan_code == ScanCode(0xE01D));
+        let key = KeyCode::from_key_message(VirtualKey::Alt, 0x20380001);
+        assert!(key.vkey == VirtualKey::LAlt);
+        let key = KeyCode::from_key_message(VirtualKey::Enter, 0x011C0001);
+        assert!(key.vkey == VirtualKey::NumEnter);
+        let key = KeyCode::from_key_message(VirtualKey::A, 0xC01E0001u32 as i32 as isize);
+        assert!(key.vkey == VirtualKey::A);
+        assert!(key.scan_code == ScanCode(0x1E));
+        let key = KeyCode::from_key_message(VirtualKey::Shift, 0x00000001);
+        assert!(key.vkey == VirtualKey::Shift);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn key_code_windows() {
+        assert!(KeyCode::from(VirtualKey::RCtrl).scan_code == ScanCode(0xE01D));
+        assert!(KeyCode::from(VirtualKey::Up).scan_code == ScanCode(0xE048));
+        assert!(KeyCode::from(ScanCode(0x36)).vkey == VirtualKey::RShift);
+        assert!(KeyCode::from(ScanCode(0xE01D)).vkey == VirtualKey::RCtrl);
+        assert!(KeyCode::from(ScanCode(0xE038)).vkey == VirtualKey::RAlt);
+        assert!(KeyCode::from(ScanCode(NUM_ENTER_SCAN_CODE)).vkey == VirtualKey::NumEnter);
+        assert!(VirtualKey::from(VK_OEM_102) == VirtualKey::IntlBackslash);
+        assert!(VIRTUAL_KEY::from(VirtualKey::IntlBackslash) == VK_OEM_102);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn key_code_serde() {
+        let key = KeyCode::new(VirtualKey::A, ScanCode(0x1e));
+        let json = serde_json::to_string(&key).unwrap();
+        assert!(json == r#"{"vkey":"A","scan_code":30}"#);
+        let de = serde_json::from_str::<KeyCode>(&json).unwrap();
+        assert!(de.vkey == key.vkey && de.scan_code == key.scan_code);
+    }
+}
@@ -0,0 +1,267 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+/// A sequence of key strokes such as `Ctrl+K Ctrl+C`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct KeySequence(pub Vec<Shortcut>);
+
+impl KeySequence {
+    #[inline]
+    pub fn new(strokes: impl Into<Vec<Shortcut>>) -> Self {
+        Self(strokes.into())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn strokes(&self) -> &[Shortcut] {
+        &self.0
+    }
+}
+
+impl From<Shortcut> for KeySequence {
+    #[inline]
+    fn from(value: Shortcut) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl std::fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stroke) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", stroke)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for KeySequence {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let strokes = s
+            .split_whitespace()
+            .map(|stroke| stroke.parse())
+            .collect::<Result<Vec<Shortcut>, _>>()?;
+        if strokes.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        Ok(Self(strokes))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeySequence {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeySequence {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChordResult<'a, T> {
+    Matched(&'a T),
+    Pending,
+    NoMatch,
+}
+
+/// Matches key events against registered `KeySequence`s.
+///
+/// When one sequence is a prefix of another, the longer one wins and the shorter never matches.
+#[derive(Clone, Debug)]
+pub struct ChordMatcher<T> {
+    bindings: Vec<(KeySequence, T)>,
+    timeout: Duration,
+    modifiers: Modifiers,
+    pending: Vec<Shortcut>,
+    last_stroke: Option<Instant>,
+}
+
+impl<T> ChordMatcher<T> {
+    #[inline]
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            bindings: vec![],
+            timeout,
+            modifiers: Modifiers::new(),
+            pending: vec![],
+            last_stroke: None,
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, sequence: impl Into<KeySequence>, value: T) {
+        self.bindings.push((sequence.into(), value));
+    }
+
+    #[inline]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    #[inline]
+    pub fn pending(&self) -> &[Shortcut] {
+        &self.pending
+    }
+
+    /// Drops a partial match. Held modifiers are kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_stroke = None;
+    }
+
+    /// Drops a partial match whose last stroke is older than the timeout.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let expired = self
+            .last_stroke
+            .is_some_and(|last| now.saturating_duration_since(last) > self.timeout);
+        if expired {
+            self.reset();
+        }
+        expired
+    }
+
+    pub fn process(&mut self, key: KeyCode, state: KeyState, now: Instant) -> ChordResult<'_, T> {
+        self.expire(now);
+        if let Some(m) = Modifier::from_vkey(key.vkey) {
+            if !matches!(m, Modifier::CapsLock | Modifier::NumLock) {
+                match state {
+                    KeyState::Pressed => self.modifiers.insert(m),
+                    KeyState::Released => self.modifiers.remove(m),
+                }
+            }
+            return self.status();
+        }
+        if state == KeyState::Released {
+            return self.status();
+        }
+        self.pending.push(Shortcut::new(self.modifiers, key.vkey));
+        self.last_stroke = Some(now);
+        let pending = &self.pending;
+        let mut exact = None;
+        let mut longer = false;
+        for (i, (seq, _)) in self.bindings.iter().enumerate() {
+            if !seq.0.starts_with(pending) {
+                continue;
+            }
+            if seq.len() == pending.len() {
+                exact.get_or_insert(i);
+            } else {
+                longer = true;
+            }
+        }
+        if longer {
+            return ChordResult::Pending;
+        }
+        self.reset();
+        match exact {
+            Some(i) => ChordResult::Matched(&self.bindings[i].1),
+            None => ChordResult::NoMatch,
+        }
+    }
+
+    fn status(&self) -> ChordResult<'_, T> {
+        if self.is_pending() {
+            ChordResult::Pending
+        } else {
+            ChordResult::NoMatch
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(vkey: VirtualKey) -> KeyCode {
+        KeyCode::new(vkey, ScanCode(0))
+    }
+
+    #[test]
+    fn parse_key_sequence() {
+        let seq: KeySequence = "Ctrl+K  Ctrl+C".parse().unwrap();
+        assert!(seq.len() == 2);
+        assert!(seq.0[1] == Shortcut::new(Modifier::Ctrl, VirtualKey::C));
+        assert!(seq.to_string() == "Ctrl+K Ctrl+C");
+        assert!("".parse::<KeySequence>() == Err(ParseShortcutError::Empty));
+    }
+
+    #[test]
+    fn chord_matcher() {
+        let now = Instant::now();
+        let mut m = ChordMatcher::new(Duration::from_secs(1));
+        m.add("Ctrl+K Ctrl+C".parse::<KeySequence>().unwrap(), 1);
+        m.add("Ctrl+S".parse::<Shortcut>().unwrap(), 2);
+        let pressed = KeyState::Pressed;
+        let released = KeyState::Released;
+        assert!(m.process(key(VirtualKey::LCtrl), pressed, now) == ChordResult::NoMatch);
+        assert!(m.process(key(VirtualKey::K), pressed, now) == ChordResult::Pending);
+        assert!(m.process(key(VirtualKey::K), released, now) == ChordResult::Pending);
+        assert!(m.process(key(VirtualKey::C), pressed, now) == ChordResult::Matched(&1));
+        assert!(!m.is_pending());
+        assert!(m.process(key(VirtualKey::S), pressed, now) == ChordResult::Matched(&2));
+        m.process(key(VirtualKey::LCtrl), released, now);
+        assert!(m.process(key(VirtualKey::S), pressed, now) == ChordResult::NoMatch);
+    }
+
+    #[test]
+    fn chord_matcher_timeout() {
+        let now = Instant::now();
+        let mut m = ChordMatcher::new(Duration::from_millis(500));
+        m.add("Ctrl+K Ctrl+C".parse::<KeySequence>().unwrap(), 1);
+        m.process(key(VirtualKey::RCtrl), KeyState::Pressed, now);
+        m.process(key(VirtualKey::K), KeyState::Pressed, now);
+        assert!(m.is_pending());
+        let later = now + Duration::from_millis(600);
+        assert!(m.process(key(VirtualKey::C), KeyState::Pressed, later) == ChordResult::NoMatch);
+        m.process(key(VirtualKey::K), KeyState::Pressed, later);
+        assert!(m.expire(later + Duration::from_secs(1)));
+        assert!(!m.is_pending());
+    }
+
+    #[test]
+    fn chord_matcher_mismatch() {
+        let now = Instant::now();
+        let mut m = ChordMatcher::new(Duration::from_secs(1));
+        m.add("Ctrl+K Ctrl+C".parse::<KeySequence>().unwrap(), 1);
+        m.process(key(VirtualKey::LCtrl), KeyState::Pressed, now);
+        m.process(key(VirtualKey::K), KeyState::Pressed, now);
+        assert!(m.process(key(VirtualKey::X), KeyState::Pressed, now) == ChordResult::NoMatch);
+        assert!(!m.is_pending());
+    }
+}
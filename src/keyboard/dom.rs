@@ -0,0 +1,707 @@
+use super::evdev::*;
+use super::*;
+
+fn evdev_from_dom_code(code: &str) -> Option<u32> {
+    let code = match code {
+        "Escape" => KEY_ESC,
+        "Digit1" => KEY_1,
+        "Digit2" => KEY_2,
+        "Digit3" => KEY_3,
+        "Digit4" => KEY_4,
+        "Digit5" => KEY_5,
+        "Digit6" => KEY_6,
+        "Digit7" => KEY_7,
+        "Digit8" => KEY_8,
+        "Digit9" => KEY_9,
+        "Digit0" => KEY_0,
+        "Minus" => KEY_MINUS,
+        "Equal" => KEY_EQUAL,
+        "Backspace" => KEY_BACKSPACE,
+        "Tab" => KEY_TAB,
+        "KeyA" => KEY_A,
+        "KeyB" => KEY_B,
+        "KeyC" => KEY_C,
+        "KeyD" => KEY_D,
+        "KeyE" => KEY_E,
+        "KeyF" => KEY_F,
+        "KeyG" => KEY_G,
+        "KeyH" => KEY_H,
+        "KeyI" => KEY_I,
+        "KeyJ" => KEY_J,
+        "KeyK" => KEY_K,
+        "KeyL" => KEY_L,
+        "KeyM" => KEY_M,
+        "KeyN" => KEY_N,
+        "KeyO" => KEY_O,
+        "KeyP" => KEY_P,
+        "KeyQ" => KEY_Q,
+        "KeyR" => KEY_R,
+        "KeyS" => KEY_S,
+        "KeyT" => KEY_T,
+        "KeyU" => KEY_U,
+        "KeyV" => KEY_V,
+        "KeyW" => KEY_W,
+        "KeyX" => KEY_X,
+        "KeyY" => KEY_Y,
+        "KeyZ" => KEY_Z,
+        "BracketLeft" => KEY_LEFTBRACE,
+        "BracketRight" => KEY_RIGHTBRACE,
+        "Enter" => KEY_ENTER,
+        "ControlLeft" => KEY_LEFTCTRL,
+        "Semicolon" => KEY_SEMICOLON,
+        "Quote" => KEY_APOSTROPHE,
+        "Backquote" => KEY_GRAVE,
+        "ShiftLeft" => KEY_LEFTSHIFT,
+        "Backslash" => KEY_BACKSLASH,
+        "Comma" => KEY_COMMA,
+        "Period" => KEY_DOT,
+        "Slash" => KEY_SLASH,
+        "ShiftRight" => KEY_RIGHTSHIFT,
+        "NumpadMultiply" => KEY_KPASTERISK,
+        "AltLeft" => KEY_LEFTALT,
+        "Space" => KEY_SPACE,
+        "CapsLock" => KEY_CAPSLOCK,
+        "F1" => KEY_F1,
+        "F2" => KEY_F2,
+        "F3" => KEY_F3,
+        "F4" => KEY_F4,
+        "F5" => KEY_F5,
+        "F6" => KEY_F6,
+        "F7" => KEY_F7,
+        "F8" => KEY_F8,
+        "F9" => KEY_F9,
+        "F10" => KEY_F10,
+        "F11" => KEY_F11,
+        "F12" => KEY_F12,
+        "F13" => KEY_F13,
+        "F14" => KEY_F14,
+        "F15" => KEY_F15,
+        "F16" => KEY_F16,
+        "F17" => KEY_F17,
+        "F18" => KEY_F18,
+        "F19" => KEY_F19,
+        "F20" => KEY_F20,
+        "F21" => KEY_F21,
+        "F22" => KEY_F22,
+        "F23" => KEY_F23,
+        "F24" => KEY_F24,
+        "NumLock" => KEY_NUMLOCK,
+        "ScrollLock" => KEY_SCROLLLOCK,
+        "Numpad1" => KEY_KP1,
+        "Numpad2" => KEY_KP2,
+        "Numpad3" => KEY_KP3,
+        "Numpad4" => KEY_KP4,
+        "Numpad5" => KEY_KP5,
+        "Numpad6" => KEY_KP6,
+        "Numpad7" => KEY_KP7,
+        "Numpad8" => KEY_KP8,
+        "Numpad9" => KEY_KP9,
+        "Numpad0" => KEY_KP0,
+        "NumpadSubtract" => KEY_KPMINUS,
+        "NumpadAdd" => KEY_KPPLUS,
+        "NumpadDecimal" => KEY_KPDOT,
+        "IntlBackslash" => KEY_102ND,
+        "IntlRo" => KEY_RO,
+        "Lang3" => KEY_KATAKANA,
+        "Lang4" => KEY_HIRAGANA,
+        "Convert" => KEY_HENKAN,
+        "KanaMode" => KEY_KATAKANAHIRAGANA,
+        "NonConvert" => KEY_MUHENKAN,
+        "NumpadEnter" => KEY_KPENTER,
+        "ControlRight" => KEY_RIGHTCTRL,
+        "NumpadDivide" => KEY_KPSLASH,
+        "PrintScreen" => KEY_SYSRQ,
+        "AltRight" => KEY_RIGHTALT,
+        "Home" => KEY_HOME,
+        "ArrowUp" => KEY_UP,
+        "PageUp" => KEY_PAGEUP,
+        "ArrowLeft" => KEY_LEFT,
+        "ArrowRight" => KEY_RIGHT,
+        "End" => KEY_END,
+        "ArrowDown" => KEY_DOWN,
+        "PageDown" => KEY_PAGEDOWN,
+        "Insert" => KEY_INSERT,
+        "Delete" => KEY_DELETE,
+        "AudioVolumeMute" => KEY_MUTE,
+        "AudioVolumeDown" => KEY_VOLUMEDOWN,
+        "AudioVolumeUp" => KEY_VOLUMEUP,
+        "Power" => KEY_POWER,
+        "NumpadEqual" => KEY_KPEQUAL,
+        "Pause" => KEY_PAUSE,
+        "NumpadComma" => KEY_KPCOMMA,
+        "Lang1" => KEY_HANGEUL,
+        "Lang2" => KEY_HANJA,
+        "IntlYen" => KEY_YEN,
+        "MetaLeft" => KEY_LEFTMETA,
+        "MetaRight" => KEY_RIGHTMETA,
+        "ContextMenu" => KEY_COMPOSE,
+        "BrowserStop" => KEY_STOP,
+        "LaunchApp2" => KEY_CALC,
+        "Sleep" => KEY_SLEEP,
+        "WakeUp" => KEY_WAKEUP,
+        "LaunchMail" => KEY_MAIL,
+        "BrowserFavorites" => KEY_BOOKMARKS,
+        "LaunchApp1" => KEY_COMPUTER,
+        "BrowserBack" => KEY_BACK,
+        "BrowserForward" => KEY_FORWARD,
+        "MediaTrackNext" => KEY_NEXTSONG,
+        "MediaPlayPause" => KEY_PLAYPAUSE,
+        "MediaTrackPrevious" => KEY_PREVIOUSSONG,
+        "MediaStop" => KEY_STOPCD,
+        "BrowserHome" => KEY_HOMEPAGE,
+        "BrowserRefresh" => KEY_REFRESH,
+        "BrowserSearch" => KEY_SEARCH,
+        "MediaSelect" => KEY_MEDIA,
+        "OSLeft" => KEY_LEFTMETA,
+        "OSRight" => KEY_RIGHTMETA,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn dom_code_from_evdev(code: u32) -> Option<&'static str> {
+    let code = match code {
+        KEY_ESC => "Escape",
+        KEY_1 => "Digit1",
+        KEY_2 => "Digit2",
+        KEY_3 => "Digit3",
+        KEY_4 => "Digit4",
+        KEY_5 => "Digit5",
+        KEY_6 => "Digit6",
+        KEY_7 => "Digit7",
+        KEY_8 => "Digit8",
+        KEY_9 => "Digit9",
+        KEY_0 => "Digit0",
+        KEY_MINUS => "Minus",
+        KEY_EQUAL => "Equal",
+        KEY_BACKSPACE => "Backspace",
+        KEY_TAB => "Tab",
+        KEY_A => "KeyA",
+        KEY_B => "KeyB",
+        KEY_C => "KeyC",
+        KEY_D => "KeyD",
+        KEY_E => "KeyE",
+        KEY_F => "KeyF",
+        KEY_G => "KeyG",
+        KEY_H => "KeyH",
+        KEY_I => "KeyI",
+        KEY_J => "KeyJ",
+        KEY_K => "KeyK",
+        KEY_L => "KeyL",
+        KEY_M => "KeyM",
+        KEY_N => "KeyN",
+        KEY_O => "KeyO",
+        KEY_P => "KeyP",
+        KEY_Q => "KeyQ",
+        KEY_R => "KeyR",
+        KEY_S => "KeyS",
+        KEY_T => "KeyT",
+        KEY_U => "KeyU",
+        KEY_V => "KeyV",
+        KEY_W => "KeyW",
+        KEY_X => "KeyX",
+        KEY_Y => "KeyY",
+        KEY_Z => "KeyZ",
+        KEY_LEFTBRACE => "BracketLeft",
+        KEY_RIGHTBRACE => "BracketRight",
+        KEY_ENTER => "Enter",
+        KEY_LEFTCTRL => "ControlLeft",
+        KEY_SEMICOLON => "Semicolon",
+        KEY_APOSTROPHE => "Quote",
+        KEY_GRAVE => "Backquote",
+        KEY_LEFTSHIFT => "ShiftLeft",
+        KEY_BACKSLASH => "Backslash",
+        KEY_COMMA => "Comma",
+        KEY_DOT => "Period",
+        KEY_SLASH => "Slash",
+        KEY_RIGHTSHIFT => "ShiftRight",
+        KEY_KPASTERISK => "NumpadMultiply",
+        KEY_LEFTALT => "AltLeft",
+        KEY_SPACE => "Space",
+        KEY_CAPSLOCK => "CapsLock",
+        KEY_F1 => "F1",
+        KEY_F2 => "F2",
+        KEY_F3 => "F3",
+        KEY_F4 => "F4",
+        KEY_F5 => "F5",
+        KEY_F6 => "F6",
+        KEY_F7 => "F7",
+        KEY_F8 => "F8",
+        KEY_F9 => "F9",
+        KEY_F10 => "F10",
+        KEY_F11 => "F11",
+        KEY_F12 => "F12",
+        KEY_F13 => "F13",
+        KEY_F14 => "F14",
+        KEY_F15 => "F15",
+        KEY_F16 => "F16",
+        KEY_F17 => "F17",
+        KEY_F18 => "F18",
+        KEY_F19 => "F19",
+        KEY_F20 => "F20",
+        KEY_F21 => "F21",
+        KEY_F22 => "F22",
+        KEY_F23 => "F23",
+        KEY_F24 => "F24",
+        KEY_NUMLOCK => "NumLock",
+        KEY_SCROLLLOCK => "ScrollLock",
+        KEY_KP1 => "Numpad1",
+        KEY_KP2 => "Numpad2",
+        KEY_KP3 => "Numpad3",
+        KEY_KP4 => "Numpad4",
+        KEY_KP5 => "Numpad5",
+        KEY_KP6 => "Numpad6",
+        KEY_KP7 => "Numpad7",
+        KEY_KP8 => "Numpad8",
+        KEY_KP9 => "Numpad9",
+        KEY_KP0 => "Numpad0",
+        KEY_KPMINUS => "NumpadSubtract",
+        KEY_KPPLUS => "NumpadAdd",
+        KEY_KPDOT => "NumpadDecimal",
+        KEY_102ND => "IntlBackslash",
+        KEY_RO => "IntlRo",
+        KEY_KATAKANA => "Lang3",
+        KEY_HIRAGANA => "Lang4",
+        KEY_HENKAN => "Convert",
+        KEY_KATAKANAHIRAGANA => "KanaMode",
+        KEY_MUHENKAN => "NonConvert",
+        KEY_KPENTER => "NumpadEnter",
+        KEY_RIGHTCTRL => "ControlRight",
+        KEY_KPSLASH => "NumpadDivide",
+        KEY_SYSRQ => "PrintScreen",
+        KEY_RIGHTALT => "AltRight",
+        KEY_HOME => "Home",
+        KEY_UP => "ArrowUp",
+        KEY_PAGEUP => "PageUp",
+        KEY_LEFT => "ArrowLeft",
+        KEY_RIGHT => "ArrowRight",
+        KEY_END => "End",
+        KEY_DOWN => "ArrowDown",
+        KEY_PAGEDOWN => "PageDown",
+        KEY_INSERT => "Insert",
+        KEY_DELETE => "Delete",
+        KEY_MUTE => "AudioVolumeMute",
+        KEY_VOLUMEDOWN => "AudioVolumeDown",
+        KEY_VOLUMEUP => "AudioVolumeUp",
+        KEY_POWER => "Power",
+        KEY_KPEQUAL => "NumpadEqual",
+        KEY_PAUSE => "Pause",
+        KEY_KPCOMMA => "NumpadComma",
+        KEY_HANGEUL => "Lang1",
+        KEY_HANJA => "Lang2",
+        KEY_YEN => "IntlYen",
+        KEY_LEFTMETA => "MetaLeft",
+        KEY_RIGHTMETA => "MetaRight",
+        KEY_COMPOSE => "ContextMenu",
+        KEY_STOP => "BrowserStop",
+        KEY_CALC => "LaunchApp2",
+        KEY_SLEEP => "Sleep",
+        KEY_WAKEUP => "WakeUp",
+        KEY_MAIL => "LaunchMail",
+        KEY_BOOKMARKS => "BrowserFavorites",
+        KEY_COMPUTER => "LaunchApp1",
+        KEY_BACK => "BrowserBack",
+        KEY_FORWARD => "BrowserForward",
+        KEY_NEXTSONG => "MediaTrackNext",
+        KEY_PLAYPAUSE => "MediaPlayPause",
+        KEY_PREVIOUSSONG => "MediaTrackPrevious",
+        KEY_STOPCD => "MediaStop",
+        KEY_HOMEPAGE => "BrowserHome",
+        KEY_REFRESH => "BrowserRefresh",
+        KEY_SEARCH => "BrowserSearch",
+        KEY_MEDIA => "MediaSelect",
+        _ => return None,
+    };
+    Some(code)
+}
+
+impl VirtualKey {
+    #[inline]
+    pub fn from_dom_code(code: &str) -> Option<Self> {
+        evdev_from_dom_code(code).and_then(vkey_from_evdev)
+    }
+
+    #[inline]
+    pub fn to_dom_code(&self) -> Option<&'static str> {
+        evdev_from_vkey(*self).and_then(dom_code_from_evdev)
+    }
+
+    pub fn from_dom_key(key: &str) -> Option<Self> {
+        let vkey = match key {
+            "Escape" | "Esc" => VirtualKey::Esc,
+            "Tab" => VirtualKey::Tab,
+            "CapsLock" => VirtualKey::CapsLock,
+            "Shift" => VirtualKey::Shift,
+            "Control" => VirtualKey::Ctrl,
+            "Alt" => VirtualKey::Alt,
+            "Meta" | "OS" | "Super" => VirtualKey::LMeta,
+            "ContextMenu" => VirtualKey::ContextMenu,
+            "Backspace" => VirtualKey::BackSpace,
+            "Enter" => VirtualKey::Enter,
+            " " | "Spacebar" => VirtualKey::Space,
+            "PrintScreen" => VirtualKey::PrintScreen,
+            "ScrollLock" => VirtualKey::ScrollLock,
+            "Pause" => VirtualKey::Pause,
+            "Insert" => VirtualKey::Insert,
+            "Delete" | "Del" => VirtualKey::Delete,
+            "Home" => VirtualKey::Home,
+            "End" => VirtualKey::End,
+            "PageUp" => VirtualKey::PageUp,
+            "PageDown" => VirtualKey::PageDown,
+            "ArrowUp" | "Up" => VirtualKey::Up,
+            "ArrowDown" | "Down" => VirtualKey::Down,
+            "ArrowLeft" | "Left" => VirtualKey::Left,
+            "ArrowRight" | "Right" => VirtualKey::Right,
+            "1" => VirtualKey::Key1,
+            "2" => VirtualKey::Key2,
+            "3" => VirtualKey::Key3,
+            "4" => VirtualKey::Key4,
+            "5" => VirtualKey::Key5,
+            "6" => VirtualKey::Key6,
+            "7" => VirtualKey::Key7,
+            "8" => VirtualKey::Key8,
+            "9" => VirtualKey::Key9,
+            "0" => VirtualKey::Key0,
+            "a" | "A" => VirtualKey::A,
+            "b" | "B" => VirtualKey::B,
+            "c" | "C" => VirtualKey::C,
+            "d" | "D" => VirtualKey::D,
+            "e" | "E" => VirtualKey::E,
+            "f" | "F" => VirtualKey::F,
+            "g" | "G" => VirtualKey::G,
+            "h" | "H" => VirtualKey::H,
+            "i" | "I" => VirtualKey::I,
+            "j" | "J" => VirtualKey::J,
+            "k" | "K" => VirtualKey::K,
+            "l" | "L" => VirtualKey::L,
+            "m" | "M" => VirtualKey::M,
+            "n" | "N" => VirtualKey::N,
+            "o" | "O" => VirtualKey::O,
+            "p" | "P" => VirtualKey::P,
+            "q" | "Q" => VirtualKey::Q,
+            "r" | "R" => VirtualKey::R,
+            "s" | "S" => VirtualKey::S,
+            "t" | "T" => VirtualKey::T,
+            "u" | "U" => VirtualKey::U,
+            "v" | "V" => VirtualKey::V,
+            "w" | "W" => VirtualKey::W,
+            "x" | "X" => VirtualKey::X,
+            "y" | "Y" => VirtualKey::Y,
+            "z" | "Z" => VirtualKey::Z,
+            ";" => VirtualKey::Semicolon,
+            "=" => VirtualKey::Equal,
+            "," => VirtualKey::Comma,
+            "-" => VirtualKey::Minus,
+            "." => VirtualKey::Period,
+            "/" => VirtualKey::Slash,
+            "`" => VirtualKey::Backquote,
+            "[" => VirtualKey::LBracket,
+            "\\" => VirtualKey::Backslash,
+            "]" => VirtualKey::RBracket,
+            "'" => VirtualKey::Quote,
+            "F1" => VirtualKey::F1,
+            "F2" => VirtualKey::F2,
+            "F3" => VirtualKey::F3,
+            "F4" => VirtualKey::F4,
+            "F5" => VirtualKey::F5,
+            "F6" => VirtualKey::F6,
+            "F7" => VirtualKey::F7,
+            "F8" => VirtualKey::F8,
+            "F9" => VirtualKey::F9,
+            "F10" => VirtualKey::F10,
+            "F11" => VirtualKey::F11,
+            "F12" => VirtualKey::F12,
+            "F13" => VirtualKey::F13,
+            "F14" => VirtualKey::F14,
+            "F15" => VirtualKey::F15,
+            "F16" => VirtualKey::F16,
+            "F17" => VirtualKey::F17,
+            "F18" => VirtualKey::F18,
+            "F19" => VirtualKey::F19,
+            "F20" => VirtualKey::F20,
+            "F21" => VirtualKey::F21,
+            "F22" => VirtualKey::F22,
+            "F23" => VirtualKey::F23,
+            "F24" => VirtualKey::F24,
+            "NumLock" => VirtualKey::NumLock,
+            "AudioVolumeMute" => VirtualKey::VolumeMute,
+            "AudioVolumeDown" => VirtualKey::VolumeDown,
+            "AudioVolumeUp" => VirtualKey::VolumeUp,
+            "MediaTrackNext" => VirtualKey::MediaNextTrack,
+            "MediaTrackPrevious" => VirtualKey::MediaPrevTrack,
+            "MediaStop" => VirtualKey::MediaStop,
+            "MediaPlayPause" => VirtualKey::MediaPlayPause,
+            "BrowserBack" => VirtualKey::BrowserBack,
+            "BrowserForward" => VirtualKey::BrowserForward,
+            "BrowserRefresh" => VirtualKey::BrowserRefresh,
+            "BrowserStop" => VirtualKey::BrowserStop,
+            "BrowserSearch" => VirtualKey::BrowserSearch,
+            "BrowserFavorites" => VirtualKey::BrowserFavorites,
+            "BrowserHome" => VirtualKey::BrowserHome,
+            "LaunchMail" => VirtualKey::LaunchMail,
+            "LaunchMediaPlayer" => VirtualKey::LaunchMediaSelect,
+            "LaunchApplication1" => VirtualKey::LaunchApp1,
+            "LaunchApplication2" => VirtualKey::LaunchApp2,
+            _ => return None,
+        };
+        Some(vkey)
+    }
+
+    pub fn to_dom_key(&self) -> Option<&'static str> {
+        let key = match self {
+            VirtualKey::Esc => "Escape",
+            VirtualKey::Tab => "Tab",
+            VirtualKey::CapsLock => "CapsLock",
+            VirtualKey::Shift => "Shift",
+            VirtualKey::Ctrl => "Control",
+            VirtualKey::Alt => "Alt",
+            VirtualKey::LShift | VirtualKey::RShift => "Shift",
+            VirtualKey::LCtrl | VirtualKey::RCtrl => "Control",
+            VirtualKey::LAlt | VirtualKey::RAlt => "Alt",
+            VirtualKey::LMeta | VirtualKey::RMeta => "Meta",
+            VirtualKey::ContextMenu => "ContextMenu",
+            VirtualKey::BackSpace => "Backspace",
+            VirtualKey::Enter => "Enter",
+            VirtualKey::Space => " ",
+            VirtualKey::PrintScreen => "PrintScreen",
+            VirtualKey::ScrollLock => "ScrollLock",
+            VirtualKey::Pause => "Pause",
+            VirtualKey::Insert => "Insert",
+            VirtualKey::Delete => "Delete",
+            VirtualKey::Home => "Home",
+            VirtualKey::End => "End",
+            VirtualKey::PageUp => "PageUp",
+            VirtualKey::PageDown => "PageDown",
+            VirtualKey::Up => "ArrowUp",
+            VirtualKey::Down => "ArrowDown",
+            VirtualKey::Left => "ArrowLeft",
+            VirtualKey::Right => "ArrowRight",
+            VirtualKey::Key1 => "1",
+            VirtualKey::Key2 => "2",
+            VirtualKey::Key3 => "3",
+            VirtualKey::Key4 => "4",
+            VirtualKey::Key5 => "5",
+            VirtualKey::Key6 => "6",
+            VirtualKey::Key7 => "7",
+            VirtualKey::Key8 => "8",
+            VirtualKey::Key9 => "9",
+            VirtualKey::Key0 => "0",
+            VirtualKey::A => "a",
+            VirtualKey::B => "b",
+            VirtualKey::C => "c",
+            VirtualKey::D => "d",
+            VirtualKey::E => "e",
+            VirtualKey::F => "f",
+            VirtualKey::G => "g",
+            VirtualKey::H => "h",
+            VirtualKey::I => "i",
+            VirtualKey::J => "j",
+            VirtualKey::K => "k",
+            VirtualKey::L => "l",
+            VirtualKey::M => "m",
+            VirtualKey::N => "n",
+            VirtualKey::O => "o",
+            VirtualKey::P => "p",
+            VirtualKey::Q => "q",
+            VirtualKey::R => "r",
+            VirtualKey::S => "s",
+            VirtualKey::T => "t",
+            VirtualKey::U => "u",
+            VirtualKey::V => "v",
+            VirtualKey::W => "w",
+            VirtualKey::X => "x",
+            VirtualKey::Y => "y",
+            VirtualKey::Z => "z",
+            VirtualKey::Semicolon => ";",
+            VirtualKey::Equal => "=",
+            VirtualKey::Comma => ",",
+            VirtualKey::Minus => "-",
+            VirtualKey::Period => ".",
+            VirtualKey::Slash => "/",
+            VirtualKey::Backquote => "`",
+            VirtualKey::LBracket => "[",
+            VirtualKey::Backslash => "\\",
+            VirtualKey::RBracket => "]",
+            VirtualKey::Quote => "'",
+            // The character depends on the layout, e.g. `<` on German or `\` on UK keyboards.
+            VirtualKey::IntlBackslash => return None,
+            VirtualKey::F1 => "F1",
+            VirtualKey::F2 => "F2",
+            VirtualKey::F3 => "F3",
+            VirtualKey::F4 => "F4",
+            VirtualKey::F5 => "F5",
+            VirtualKey::F6 => "F6",
+            VirtualKey::F7 => "F7",
+            VirtualKey::F8 => "F8",
+            VirtualKey::F9 => "F9",
+            VirtualKey::F10 => "F10",
+            VirtualKey::F11 => "F11",
+            VirtualKey::F12 => "F12",
+            VirtualKey::F13 => "F13",
+            VirtualKey::F14 => "F14",
+            VirtualKey::F15 => "F15",
+            VirtualKey::F16 => "F16",
+            VirtualKey::F17 => "F17",
+            VirtualKey::F18 => "F18",
+            VirtualKey::F19 => "F19",
+            VirtualKey::F20 => "F20",
+            VirtualKey::F21 => "F21",
+            VirtualKey::F22 => "F22",
+            VirtualKey::F23 => "F23",
+            VirtualKey::F24 => "F24",
+            VirtualKey::NumLock => "NumLock",
+            VirtualKey::NumAdd => "+",
+            VirtualKey::NumSub => "-",
+            VirtualKey::NumMul => "*",
+            VirtualKey::NumDiv => "/",
+            VirtualKey::NumDecimal => ".",
+            VirtualKey::NumEnter => "Enter",
+            VirtualKey::VolumeMute => "AudioVolumeMute",
+            VirtualKey::VolumeDown => "AudioVolumeDown",
+            VirtualKey::VolumeUp => "AudioVolumeUp",
+            VirtualKey::MediaNextTrack => "MediaTrackNext",
+            VirtualKey::MediaPrevTrack => "MediaTrackPrevious",
+            VirtualKey::MediaStop => "MediaStop",
+            VirtualKey::MediaPlayPause => "MediaPlayPause",
+            VirtualKey::BrowserBack => "BrowserBack",
+            VirtualKey::BrowserForward => "BrowserForward",
+            VirtualKey::BrowserRefresh => "BrowserRefresh",
+            VirtualKey::BrowserStop => "BrowserStop",
+            VirtualKey::BrowserSearch => "BrowserSearch",
+            VirtualKey::BrowserFavorites => "BrowserFavorites",
+            VirtualKey::BrowserHome => "BrowserHome",
+            VirtualKey::LaunchMail => "LaunchMail",
+            VirtualKey::LaunchMediaSelect => "LaunchMediaPlayer",
+            VirtualKey::LaunchApp1 => "LaunchApplication1",
+            VirtualKey::LaunchApp2 => "LaunchApplication2",
+            VirtualKey::Num1 => "1",
+            VirtualKey::Num2 => "2",
+            VirtualKey::Num3 => "3",
+            VirtualKey::Num4 => "4",
+            VirtualKey::Num5 => "5",
+            VirtualKey::Num6 => "6",
+            VirtualKey::Num7 => "7",
+            VirtualKey::Num8 => "8",
+            VirtualKey::Num9 => "9",
+            VirtualKey::Num0 => "0",
+            VirtualKey::Other(_) => return None,
+        };
+        Some(key)
+    }
+}
+
+impl ScanCode {
+    #[inline]
+    pub fn from_dom_code(code: &str) -> Option<Self> {
+        evdev_from_dom_code(code).and_then(ScanCode::from_evdev)
+    }
+
+    #[inline]
+    pub fn to_dom_code(&self) -> Option<&'static str> {
+        self.to_evdev().and_then(dom_code_from_evdev)
+    }
+}
+
+impl KeyCode {
+    #[inline]
+    pub fn from_dom_code(code: &str) -> Option<Self> {
+        let code = evdev_from_dom_code(code)?;
+        Some(KeyCode::from_evdev(code))
+    }
+
+    /// Builds a `KeyCode` from a `KeyboardEvent`'s `code` and `key`.
+    ///
+    /// `key` decides the virtual key for printable characters so that the keyboard layout is
+    /// respected; everything else, and all numpad keys, go by `code`.
+    pub fn from_dom(code: &str, key: &str) -> Option<Self> {
+        let by_code = VirtualKey::from_dom_code(code);
+        let by_key = VirtualKey::from_dom_key(key);
+        let printable = key.chars().count() == 1 && !code.starts_with("Numpad");
+        let vkey = if printable {
+            by_key.or(by_code)
+        } else {
+            by_code.or(by_key)
+        };
+        let scan_code = ScanCode::from_dom_code(code);
+        if vkey.is_none() && scan_code.is_none() {
+            return None;
+        }
+        Some(KeyCode {
+            vkey: vkey.unwrap_or(VirtualKey::Other(0)),
+            scan_code: scan_code.unwrap_or(ScanCode(0)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dom_code_to_vkey() {
+        assert!(VirtualKey::from_dom_code("KeyA") == Some(VirtualKey::A));
+        assert!(VirtualKey::from_dom_code("NumpadAdd") == Some(VirtualKey::NumAdd));
+        assert!(VirtualKey::from_dom_code("ArrowUp") == Some(VirtualKey::Up));
+        assert!(VirtualKey::from_dom_code("Escape") == Some(VirtualKey::Esc));
+        assert!(VirtualKey::from_dom_code("Unidentified").is_none());
+        assert!(VirtualKey::Num5.to_dom_code() == Some("Numpad5"));
+        assert!(VirtualKey::Shift.to_dom_code() == Some("ShiftLeft"));
+        assert!(VirtualKey::from_dom_code("ShiftRight") == Some(VirtualKey::RShift));
+        assert!(VirtualKey::from_dom_code("NumpadEnter") == Some(VirtualKey::NumEnter));
+        assert!(VirtualKey::from_dom_code("Quote") == Some(VirtualKey::Quote));
+        assert!(VirtualKey::from_dom_code("MediaPlayPause") == Some(VirtualKey::MediaPlayPause));
+    }
+
+    #[test]
+    fn dom_code_round_trip() {
+        for code in 0..0x300 {
+            if let Some(s) = dom_code_from_evdev(code) {
+                assert!(evdev_from_dom_code(s) == Some(code));
+            }
+        }
+    }
+
+    #[test]
+    fn dom_key_to_vkey() {
+        assert!(VirtualKey::from_dom_key("Escape") == Some(VirtualKey::Esc));
+        assert!(VirtualKey::from_dom_key("Esc") == Some(VirtualKey::Esc));
+        assert!(VirtualKey::from_dom_key("q") == Some(VirtualKey::Q));
+        assert!(VirtualKey::from_dom_key("Q") == Some(VirtualKey::Q));
+        assert!(VirtualKey::from_dom_key(" ") == Some(VirtualKey::Space));
+        assert!(VirtualKey::from_dom_key("ArrowLeft") == Some(VirtualKey::Left));
+        assert!(VirtualKey::Ctrl.to_dom_key() == Some("Control"));
+        assert!(VirtualKey::Num3.to_dom_key() == Some("3"));
+        assert!(VirtualKey::from_dom_key("/") == Some(VirtualKey::Slash));
+        assert!(VirtualKey::from_dom_key("Meta") == Some(VirtualKey::LMeta));
+        assert!(VirtualKey::RCtrl.to_dom_key() == Some("Control"));
+        assert!(VirtualKey::VolumeUp.to_dom_key() == Some("AudioVolumeUp"));
+    }
+
+    #[test]
+    fn key_code_from_dom() {
+        let key = KeyCode::from_dom("KeyQ", "a").unwrap();
+        assert!(key.vkey == VirtualKey::A);
+        assert!(key.scan_code == ScanCode(0x10));
+        let key = KeyCode::from_dom("Numpad1", "1").unwrap();
+        assert!(key.vkey == VirtualKey::Num1);
+        let key = KeyCode::from_dom("Numpad1", "End").unwrap();
+        assert!(key.vkey == VirtualKey::Num1);
+        let key = KeyCode::from_dom_code("ControlRight").unwrap();
+        assert!(key.scan_code == ScanCode(0xE01D));
+        assert!(KeyCode::from_dom("", "Unidentified").is_none());
+        // Shifted punctuation goes by `code`.
+        for (code, key, vkey) in [
+            ("Equal", "+", VirtualKey::Equal),
+            ("Digit8", "*", VirtualKey::Key8),
+            ("Digit1", "!", VirtualKey::Key1),
+            ("Minus", "_", VirtualKey::Minus),
+            ("Semicolon", ":", VirtualKey::Semicolon),
+            ("BracketLeft", "{", VirtualKey::LBracket),
+        ] {
+            assert!(KeyCode::from_dom(code, key).unwrap().vkey == vkey);
+        }
+        assert!(KeyCode::from_dom("NumpadAdd", "+").unwrap().vkey == VirtualKey::NumAdd);
+        assert!(KeyCode::from_dom("NumpadMultiply", "*").unwrap().vkey == VirtualKey::NumMul);
+        assert!(VirtualKey::from_dom_key("+").is_none());
+    }
+}
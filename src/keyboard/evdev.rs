@@ -0,0 +1,693 @@
+use super::*;
+
+// Key codes from linux/input-event-codes.h.
+pub(super) const KEY_ESC: u32 = 1;
+pub(super) const KEY_1: u32 = 2;
+pub(super) const KEY_2: u32 = 3;
+pub(super) const KEY_3: u32 = 4;
+pub(super) const KEY_4: u32 = 5;
+pub(super) const KEY_5: u32 = 6;
+pub(super) const KEY_6: u32 = 7;
+pub(super) const KEY_7: u32 = 8;
+pub(super) const KEY_8: u32 = 9;
+pub(super) const KEY_9: u32 = 10;
+pub(super) const KEY_0: u32 = 11;
+pub(super) const KEY_MINUS: u32 = 12;
+pub(super) const KEY_EQUAL: u32 = 13;
+pub(super) const KEY_BACKSPACE: u32 = 14;
+pub(super) const KEY_TAB: u32 = 15;
+pub(super) const KEY_Q: u32 = 16;
+pub(super) const KEY_W: u32 = 17;
+pub(super) const KEY_E: u32 = 18;
+pub(super) const KEY_R: u32 = 19;
+pub(super) const KEY_T: u32 = 20;
+pub(super) const KEY_Y: u32 = 21;
+pub(super) const KEY_U: u32 = 22;
+pub(super) const KEY_I: u32 = 23;
+pub(super) const KEY_O: u32 = 24;
+pub(super) const KEY_P: u32 = 25;
+pub(super) const KEY_LEFTBRACE: u32 = 26;
+pub(super) const KEY_RIGHTBRACE: u32 = 27;
+pub(super) const KEY_ENTER: u32 = 28;
+pub(super) const KEY_LEFTCTRL: u32 = 29;
+pub(super) const KEY_A: u32 = 30;
+pub(super) const KEY_S: u32 = 31;
+pub(super) const KEY_D: u32 = 32;
+pub(super) const KEY_F: u32 = 33;
+pub(super) const KEY_G: u32 = 34;
+pub(super) const KEY_H: u32 = 35;
+pub(super) const KEY_J: u32 = 36;
+pub(super) const KEY_K: u32 = 37;
+pub(super) const KEY_L: u32 = 38;
+pub(super) const KEY_SEMICOLON: u32 = 39;
+pub(super) const KEY_APOSTROPHE: u32 = 40;
+pub(super) const KEY_GRAVE: u32 = 41;
+pub(super) const KEY_LEFTSHIFT: u32 = 42;
+pub(super) const KEY_BACKSLASH: u32 = 43;
+pub(super) const KEY_Z: u32 = 44;
+pub(super) const KEY_X: u32 = 45;
+pub(super) const KEY_C: u32 = 46;
+pub(super) const KEY_V: u32 = 47;
+pub(super) const KEY_B: u32 = 48;
+pub(super) const KEY_N: u32 = 49;
+pub(super) const KEY_M: u32 = 50;
+pub(super) const KEY_COMMA: u32 = 51;
+pub(super) const KEY_DOT: u32 = 52;
+pub(super) const KEY_SLASH: u32 = 53;
+pub(super) const KEY_RIGHTSHIFT: u32 = 54;
+pub(super) const KEY_KPASTERISK: u32 = 55;
+pub(super) const KEY_LEFTALT: u32 = 56;
+pub(super) const KEY_SPACE: u32 = 57;
+pub(super) const KEY_CAPSLOCK: u32 = 58;
+pub(super) const KEY_F1: u32 = 59;
+pub(super) const KEY_F2: u32 = 60;
+pub(super) const KEY_F3: u32 = 61;
+pub(super) const KEY_F4: u32 = 62;
+pub(super) const KEY_F5: u32 = 63;
+pub(super) const KEY_F6: u32 = 64;
+pub(super) const KEY_F7: u32 = 65;
+pub(super) const KEY_F8: u32 = 66;
+pub(super) const KEY_F9: u32 = 67;
+pub(super) const KEY_F10: u32 = 68;
+pub(super) const KEY_NUMLOCK: u32 = 69;
+pub(super) const KEY_SCROLLLOCK: u32 = 70;
+pub(super) const KEY_KP7: u32 = 71;
+pub(super) const KEY_KP8: u32 = 72;
+pub(super) const KEY_KP9: u32 = 73;
+pub(super) const KEY_KPMINUS: u32 = 74;
+pub(super) const KEY_KP4: u32 = 75;
+pub(super) const KEY_KP5: u32 = 76;
+pub(super) const KEY_KP6: u32 = 77;
+pub(super) const KEY_KPPLUS: u32 = 78;
+pub(super) const KEY_KP1: u32 = 79;
+pub(super) const KEY_KP2: u32 = 80;
+pub(super) const KEY_KP3: u32 = 81;
+pub(super) const KEY_KP0: u32 = 82;
+pub(super) const KEY_KPDOT: u32 = 83;
+pub(super) const KEY_ZENKAKUHANKAKU: u32 = 85;
+pub(super) const KEY_102ND: u32 = 86;
+pub(super) const KEY_F11: u32 = 87;
+pub(super) const KEY_F12: u32 = 88;
+pub(super) const KEY_RO: u32 = 89;
+pub(super) const KEY_KATAKANA: u32 = 90;
+pub(super) const KEY_HIRAGANA: u32 = 91;
+pub(super) const KEY_HENKAN: u32 = 92;
+pub(super) const KEY_KATAKANAHIRAGANA: u32 = 93;
+pub(super) const KEY_MUHENKAN: u32 = 94;
+pub(super) const KEY_KPJPCOMMA: u32 = 95;
+pub(super) const KEY_KPENTER: u32 = 96;
+pub(super) const KEY_RIGHTCTRL: u32 = 97;
+pub(super) const KEY_KPSLASH: u32 = 98;
+pub(super) const KEY_SYSRQ: u32 = 99;
+pub(super) const KEY_RIGHTALT: u32 = 100;
+pub(super) const KEY_HOME: u32 = 102;
+pub(super) const KEY_UP: u32 = 103;
+pub(super) const KEY_PAGEUP: u32 = 104;
+pub(super) const KEY_LEFT: u32 = 105;
+pub(super) const KEY_RIGHT: u32 = 106;
+pub(super) const KEY_END: u32 = 107;
+pub(super) const KEY_DOWN: u32 = 108;
+pub(super) const KEY_PAGEDOWN: u32 = 109;
+pub(super) const KEY_INSERT: u32 = 110;
+pub(super) const KEY_DELETE: u32 = 111;
+pub(super) const KEY_MUTE: u32 = 113;
+pub(super) const KEY_VOLUMEDOWN: u32 = 114;
+pub(super) const KEY_VOLUMEUP: u32 = 115;
+pub(super) const KEY_POWER: u32 = 116;
+pub(super) const KEY_KPEQUAL: u32 = 117;
+pub(super) const KEY_PAUSE: u32 = 119;
+pub(super) const KEY_KPCOMMA: u32 = 121;
+pub(super) const KEY_HANGEUL: u32 = 122;
+pub(super) const KEY_HANJA: u32 = 123;
+pub(super) const KEY_YEN: u32 = 124;
+pub(super) const KEY_LEFTMETA: u32 = 125;
+pub(super) const KEY_RIGHTMETA: u32 = 126;
+pub(super) const KEY_COMPOSE: u32 = 127;
+pub(super) const KEY_STOP: u32 = 128;
+pub(super) const KEY_AGAIN: u32 = 129;
+pub(super) const KEY_PROPS: u32 = 130;
+pub(super) const KEY_UNDO: u32 = 131;
+pub(super) const KEY_FRONT: u32 = 132;
+pub(super) const KEY_COPY: u32 = 133;
+pub(super) const KEY_OPEN: u32 = 134;
+pub(super) const KEY_PASTE: u32 = 135;
+pub(super) const KEY_FIND: u32 = 136;
+pub(super) const KEY_CUT: u32 = 137;
+pub(super) const KEY_HELP: u32 = 138;
+pub(super) const KEY_CALC: u32 = 140;
+pub(super) const KEY_SLEEP: u32 = 142;
+pub(super) const KEY_WAKEUP: u32 = 143;
+pub(super) const KEY_MAIL: u32 = 155;
+pub(super) const KEY_BOOKMARKS: u32 = 156;
+pub(super) const KEY_COMPUTER: u32 = 157;
+pub(super) const KEY_BACK: u32 = 158;
+pub(super) const KEY_FORWARD: u32 = 159;
+pub(super) const KEY_NEXTSONG: u32 = 163;
+pub(super) const KEY_PLAYPAUSE: u32 = 164;
+pub(super) const KEY_PREVIOUSSONG: u32 = 165;
+pub(super) const KEY_STOPCD: u32 = 166;
+pub(super) const KEY_HOMEPAGE: u32 = 172;
+pub(super) const KEY_REFRESH: u32 = 173;
+pub(super) const KEY_KPLEFTPAREN: u32 = 179;
+pub(super) const KEY_KPRIGHTPAREN: u32 = 180;
+pub(super) const KEY_F13: u32 = 183;
+pub(super) const KEY_F14: u32 = 184;
+pub(super) const KEY_F15: u32 = 185;
+pub(super) const KEY_F16: u32 = 186;
+pub(super) const KEY_F17: u32 = 187;
+pub(super) const KEY_F18: u32 = 188;
+pub(super) const KEY_F19: u32 = 189;
+pub(super) const KEY_F20: u32 = 190;
+pub(super) const KEY_F21: u32 = 191;
+pub(super) const KEY_F22: u32 = 192;
+pub(super) const KEY_F23: u32 = 193;
+pub(super) const KEY_F24: u32 = 194;
+pub(super) const KEY_SEARCH: u32 = 217;
+pub(super) const KEY_MEDIA: u32 = 226;
+
+pub(super) fn vkey_from_evdev(code: u32) -> Option<VirtualKey> {
+    let vkey = match code {
+        KEY_ESC => VirtualKey::Esc,
+        KEY_1 => VirtualKey::Key1,
+        KEY_2 => VirtualKey::Key2,
+        KEY_3 => VirtualKey::Key3,
+        KEY_4 => VirtualKey::Key4,
+        KEY_5 => VirtualKey::Key5,
+        KEY_6 => VirtualKey::Key6,
+        KEY_7 => VirtualKey::Key7,
+        KEY_8 => VirtualKey::Key8,
+        KEY_9 => VirtualKey::Key9,
+        KEY_0 => VirtualKey::Key0,
+        KEY_A => VirtualKey::A,
+        KEY_B => VirtualKey::B,
+        KEY_C => VirtualKey::C,
+        KEY_D => VirtualKey::D,
+        KEY_E => VirtualKey::E,
+        KEY_F => VirtualKey::F,
+        KEY_G => VirtualKey::G,
+        KEY_H => VirtualKey::H,
+        KEY_I => VirtualKey::I,
+        KEY_J => VirtualKey::J,
+        KEY_K => VirtualKey::K,
+        KEY_L => VirtualKey::L,
+        KEY_M => VirtualKey::M,
+        KEY_N => VirtualKey::N,
+        KEY_O => VirtualKey::O,
+        KEY_P => VirtualKey::P,
+        KEY_Q => VirtualKey::Q,
+        KEY_R => VirtualKey::R,
+        KEY_S => VirtualKey::S,
+        KEY_T => VirtualKey::T,
+        KEY_U => VirtualKey::U,
+        KEY_V => VirtualKey::V,
+        KEY_W => VirtualKey::W,
+        KEY_X => VirtualKey::X,
+        KEY_Y => VirtualKey::Y,
+        KEY_Z => VirtualKey::Z,
+        KEY_SEMICOLON => VirtualKey::Semicolon,
+        KEY_EQUAL => VirtualKey::Equal,
+        KEY_COMMA => VirtualKey::Comma,
+        KEY_MINUS => VirtualKey::Minus,
+        KEY_DOT => VirtualKey::Period,
+        KEY_SLASH => VirtualKey::Slash,
+        KEY_GRAVE => VirtualKey::Backquote,
+        KEY_LEFTBRACE => VirtualKey::LBracket,
+        KEY_BACKSLASH => VirtualKey::Backslash,
+        KEY_RIGHTBRACE => VirtualKey::RBracket,
+        KEY_APOSTROPHE => VirtualKey::Quote,
+        KEY_102ND => VirtualKey::IntlBackslash,
+        KEY_F1 => VirtualKey::F1,
+        KEY_F2 => VirtualKey::F2,
+        KEY_F3 => VirtualKey::F3,
+        KEY_F4 => VirtualKey::F4,
+        KEY_F5 => VirtualKey::F5,
+        KEY_F6 => VirtualKey::F6,
+        KEY_F7 => VirtualKey::F7,
+        KEY_F8 => VirtualKey::F8,
+        KEY_F9 => VirtualKey::F9,
+        KEY_F10 => VirtualKey::F10,
+        KEY_F11 => VirtualKey::F11,
+        KEY_F12 => VirtualKey::F12,
+        KEY_F13 => VirtualKey::F13,
+        KEY_F14 => VirtualKey::F14,
+        KEY_F15 => VirtualKey::F15,
+        KEY_F16 => VirtualKey::F16,
+        KEY_F17 => VirtualKey::F17,
+        KEY_F18 => VirtualKey::F18,
+        KEY_F19 => VirtualKey::F19,
+        KEY_F20 => VirtualKey::F20,
+        KEY_F21 => VirtualKey::F21,
+        KEY_F22 => VirtualKey::F22,
+        KEY_F23 => VirtualKey::F23,
+        KEY_F24 => VirtualKey::F24,
+        KEY_TAB => VirtualKey::Tab,
+        KEY_CAPSLOCK => VirtualKey::CapsLock,
+        KEY_LEFTSHIFT => VirtualKey::LShift,
+        KEY_RIGHTSHIFT => VirtualKey::RShift,
+        KEY_LEFTCTRL => VirtualKey::LCtrl,
+        KEY_RIGHTCTRL => VirtualKey::RCtrl,
+        KEY_LEFTALT => VirtualKey::LAlt,
+        KEY_RIGHTALT => VirtualKey::RAlt,
+        KEY_LEFTMETA => VirtualKey::LMeta,
+        KEY_RIGHTMETA => VirtualKey::RMeta,
+        KEY_COMPOSE => VirtualKey::ContextMenu,
+        KEY_BACKSPACE => VirtualKey::BackSpace,
+        KEY_ENTER => VirtualKey::Enter,
+        KEY_SPACE => VirtualKey::Space,
+        KEY_SYSRQ => VirtualKey::PrintScreen,
+        KEY_SCROLLLOCK => VirtualKey::ScrollLock,
+        KEY_PAUSE => VirtualKey::Pause,
+        KEY_INSERT => VirtualKey::Insert,
+        KEY_DELETE => VirtualKey::Delete,
+        KEY_HOME => VirtualKey::Home,
+        KEY_END => VirtualKey::End,
+        KEY_PAGEUP => VirtualKey::PageUp,
+        KEY_PAGEDOWN => VirtualKey::PageDown,
+        KEY_UP => VirtualKey::Up,
+        KEY_DOWN => VirtualKey::Down,
+        KEY_LEFT => VirtualKey::Left,
+        KEY_RIGHT => VirtualKey::Right,
+        KEY_NUMLOCK => VirtualKey::NumLock,
+        KEY_KP1 => VirtualKey::Num1,
+        KEY_KP2 => VirtualKey::Num2,
+        KEY_KP3 => VirtualKey::Num3,
+        KEY_KP4 => VirtualKey::Num4,
+        KEY_KP5 => VirtualKey::Num5,
+        KEY_KP6 => VirtualKey::Num6,
+        KEY_KP7 => VirtualKey::Num7,
+        KEY_KP8 => VirtualKey::Num8,
+        KEY_KP9 => VirtualKey::Num9,
+        KEY_KP0 => VirtualKey::Num0,
+        KEY_KPPLUS => VirtualKey::NumAdd,
+        KEY_KPMINUS => VirtualKey::NumSub,
+        KEY_KPASTERISK => VirtualKey::NumMul,
+        KEY_KPSLASH => VirtualKey::NumDiv,
+        KEY_KPDOT => VirtualKey::NumDecimal,
+        KEY_KPENTER => VirtualKey::NumEnter,
+        KEY_MUTE => VirtualKey::VolumeMute,
+        KEY_VOLUMEDOWN => VirtualKey::VolumeDown,
+        KEY_VOLUMEUP => VirtualKey::VolumeUp,
+        KEY_NEXTSONG => VirtualKey::MediaNextTrack,
+        KEY_PREVIOUSSONG => VirtualKey::MediaPrevTrack,
+        KEY_STOPCD => VirtualKey::MediaStop,
+        KEY_PLAYPAUSE => VirtualKey::MediaPlayPause,
+        KEY_BACK => VirtualKey::BrowserBack,
+        KEY_FORWARD => VirtualKey::BrowserForward,
+        KEY_REFRESH => VirtualKey::BrowserRefresh,
+        KEY_STOP => VirtualKey::BrowserStop,
+        KEY_SEARCH => VirtualKey::BrowserSearch,
+        KEY_BOOKMARKS => VirtualKey::BrowserFavorites,
+        KEY_HOMEPAGE => VirtualKey::BrowserHome,
+        KEY_MAIL => VirtualKey::LaunchMail,
+        KEY_MEDIA => VirtualKey::LaunchMediaSelect,
+        KEY_COMPUTER => VirtualKey::LaunchApp1,
+        KEY_CALC => VirtualKey::LaunchApp2,
+        _ => return None,
+    };
+    Some(vkey)
+}
+
+pub(super) fn evdev_from_vkey(vkey: VirtualKey) -> Option<u32> {
+    let code = match vkey {
+        VirtualKey::Esc => KEY_ESC,
+        VirtualKey::Key1 => KEY_1,
+        VirtualKey::Key2 => KEY_2,
+        VirtualKey::Key3 => KEY_3,
+        VirtualKey::Key4 => KEY_4,
+        VirtualKey::Key5 => KEY_5,
+        VirtualKey::Key6 => KEY_6,
+        VirtualKey::Key7 => KEY_7,
+        VirtualKey::Key8 => KEY_8,
+        VirtualKey::Key9 => KEY_9,
+        VirtualKey::Key0 => KEY_0,
+        VirtualKey::A => KEY_A,
+        VirtualKey::B => KEY_B,
+        VirtualKey::C => KEY_C,
+        VirtualKey::D => KEY_D,
+        VirtualKey::E => KEY_E,
+        VirtualKey::F => KEY_F,
+        VirtualKey::G => KEY_G,
+        VirtualKey::H => KEY_H,
+        VirtualKey::I => KEY_I,
+        VirtualKey::J => KEY_J,
+        VirtualKey::K => KEY_K,
+        VirtualKey::L => KEY_L,
+        VirtualKey::M => KEY_M,
+        VirtualKey::N => KEY_N,
+        VirtualKey::O => KEY_O,
+        VirtualKey::P => KEY_P,
+        VirtualKey::Q => KEY_Q,
+        VirtualKey::R => KEY_R,
+        VirtualKey::S => KEY_S,
+        VirtualKey::T => KEY_T,
+        VirtualKey::U => KEY_U,
+        VirtualKey::V => KEY_V,
+        VirtualKey::W => KEY_W,
+        VirtualKey::X => KEY_X,
+        VirtualKey::Y => KEY_Y,
+        VirtualKey::Z => KEY_Z,
+        VirtualKey::Semicolon => KEY_SEMICOLON,
+        VirtualKey::Equal => KEY_EQUAL,
+        VirtualKey::Comma => KEY_COMMA,
+        VirtualKey::Minus => KEY_MINUS,
+        VirtualKey::Period => KEY_DOT,
+        VirtualKey::Slash => KEY_SLASH,
+        VirtualKey::Backquote => KEY_GRAVE,
+        VirtualKey::LBracket => KEY_LEFTBRACE,
+        VirtualKey::Backslash => KEY_BACKSLASH,
+        VirtualKey::RBracket => KEY_RIGHTBRACE,
+        VirtualKey::Quote => KEY_APOSTROPHE,
+        VirtualKey::IntlBackslash => KEY_102ND,
+        VirtualKey::F1 => KEY_F1,
+        VirtualKey::F2 => KEY_F2,
+        VirtualKey::F3 => KEY_F3,
+        VirtualKey::F4 => KEY_F4,
+        VirtualKey::F5 => KEY_F5,
+        VirtualKey::F6 => KEY_F6,
+        VirtualKey::F7 => KEY_F7,
+        VirtualKey::F8 => KEY_F8,
+        VirtualKey::F9 => KEY_F9,
+        VirtualKey::F10 => KEY_F10,
+        VirtualKey::F11 => KEY_F11,
+        VirtualKey::F12 => KEY_F12,
+        VirtualKey::F13 => KEY_F13,
+        VirtualKey::F14 => KEY_F14,
+        VirtualKey::F15 => KEY_F15,
+        VirtualKey::F16 => KEY_F16,
+        VirtualKey::F17 => KEY_F17,
+        VirtualKey::F18 => KEY_F18,
+        VirtualKey::F19 => KEY_F19,
+        VirtualKey::F20 => KEY_F20,
+        VirtualKey::F21 => KEY_F21,
+        VirtualKey::F22 => KEY_F22,
+        VirtualKey::F23 => KEY_F23,
+        VirtualKey::F24 => KEY_F24,
+        VirtualKey::Tab => KEY_TAB,
+        VirtualKey::CapsLock => KEY_CAPSLOCK,
+        VirtualKey::Shift => KEY_LEFTSHIFT,
+        VirtualKey::Ctrl => KEY_LEFTCTRL,
+        VirtualKey::Alt => KEY_LEFTALT,
+        VirtualKey::LShift => KEY_LEFTSHIFT,
+        VirtualKey::RShift => KEY_RIGHTSHIFT,
+        VirtualKey::LCtrl => KEY_LEFTCTRL,
+        VirtualKey::RCtrl => KEY_RIGHTCTRL,
+        VirtualKey::LAlt => KEY_LEFTALT,
+        VirtualKey::RAlt => KEY_RIGHTALT,
+        VirtualKey::LMeta => KEY_LEFTMETA,
+        VirtualKey::RMeta => KEY_RIGHTMETA,
+        VirtualKey::ContextMenu => KEY_COMPOSE,
+        VirtualKey::BackSpace => KEY_BACKSPACE,
+        VirtualKey::Enter => KEY_ENTER,
+        VirtualKey::Space => KEY_SPACE,
+        VirtualKey::PrintScreen => KEY_SYSRQ,
+        VirtualKey::ScrollLock => KEY_SCROLLLOCK,
+        VirtualKey::Pause => KEY_PAUSE,
+        VirtualKey::Insert => KEY_INSERT,
+        VirtualKey::Delete => KEY_DELETE,
+        VirtualKey::Home => KEY_HOME,
+        VirtualKey::End => KEY_END,
+        VirtualKey::PageUp => KEY_PAGEUP,
+        VirtualKey::PageDown => KEY_PAGEDOWN,
+        VirtualKey::Up => KEY_UP,
+        VirtualKey::Down => KEY_DOWN,
+        VirtualKey::Left => KEY_LEFT,
+        VirtualKey::Right => KEY_RIGHT,
+        VirtualKey::NumLock => KEY_NUMLOCK,
+        VirtualKey::Num1 => KEY_KP1,
+        VirtualKey::Num2 => KEY_KP2,
+        VirtualKey::Num3 => KEY_KP3,
+        VirtualKey::Num4 => KEY_KP4,
+        VirtualKey::Num5 => KEY_KP5,
+        VirtualKey::Num6 => KEY_KP6,
+        VirtualKey::Num7 => KEY_KP7,
+        VirtualKey::Num8 => KEY_KP8,
+        VirtualKey::Num9 => KEY_KP9,
+        VirtualKey::Num0 => KEY_KP0,
+        VirtualKey::NumAdd => KEY_KPPLUS,
+        VirtualKey::NumSub => KEY_KPMINUS,
+        VirtualKey::NumMul => KEY_KPASTERISK,
+        VirtualKey::NumDiv => KEY_KPSLASH,
+        VirtualKey::NumDecimal => KEY_KPDOT,
+        VirtualKey::NumEnter => KEY_KPENTER,
+        VirtualKey::VolumeMute => KEY_MUTE,
+        VirtualKey::VolumeDown => KEY_VOLUMEDOWN,
+        VirtualKey::VolumeUp => KEY_VOLUMEUP,
+        VirtualKey::MediaNextTrack => KEY_NEXTSONG,
+        VirtualKey::MediaPrevTrack => KEY_PREVIOUSSONG,
+        VirtualKey::MediaStop => KEY_STOPCD,
+        VirtualKey::MediaPlayPause => KEY_PLAYPAUSE,
+        VirtualKey::BrowserBack => KEY_BACK,
+        VirtualKey::BrowserForward => KEY_FORWARD,
+        VirtualKey::BrowserRefresh => KEY_REFRESH,
+        VirtualKey::BrowserStop => KEY_STOP,
+        VirtualKey::BrowserSearch => KEY_SEARCH,
+        VirtualKey::BrowserFavorites => KEY_BOOKMARKS,
+        VirtualKey::BrowserHome => KEY_HOMEPAGE,
+        VirtualKey::LaunchMail => KEY_MAIL,
+        VirtualKey::LaunchMediaSelect => KEY_MEDIA,
+        VirtualKey::LaunchApp1 => KEY_COMPUTER,
+        VirtualKey::LaunchApp2 => KEY_CALC,
+        VirtualKey::Other(_) => return None,
+    };
+    Some(code)
+}
+
+pub(super) fn scan_code_from_evdev(code: u32) -> Option<ScanCode> {
+    let scan_code = match code {
+        KEY_ESC..=KEY_KPDOT | KEY_102ND..=KEY_F12 => code,
+        KEY_RO => 0x73,
+        KEY_KATAKANA => 0x78,
+        KEY_HIRAGANA => 0x77,
+        KEY_HENKAN => 0x79,
+        KEY_KATAKANAHIRAGANA => 0x70,
+        KEY_MUHENKAN => 0x7B,
+        KEY_KPJPCOMMA => 0x5C,
+        KEY_KPENTER => 0xE01C,
+        KEY_RIGHTCTRL => 0xE01D,
+        KEY_KPSLASH => 0xE035,
+        KEY_SYSRQ => 0xE037,
+        KEY_RIGHTALT => 0xE038,
+        KEY_HOME => 0xE047,
+        KEY_UP => 0xE048,
+        KEY_PAGEUP => 0xE049,
+        KEY_LEFT => 0xE04B,
+        KEY_RIGHT => 0xE04D,
+        KEY_END => 0xE04F,
+        KEY_DOWN => 0xE050,
+        KEY_PAGEDOWN => 0xE051,
+        KEY_INSERT => 0xE052,
+        KEY_DELETE => 0xE053,
+        KEY_MUTE => 0xE020,
+        KEY_VOLUMEDOWN => 0xE02E,
+        KEY_VOLUMEUP => 0xE030,
+        KEY_POWER => 0xE05E,
+        KEY_KPEQUAL => 0x59,
+        KEY_PAUSE => 0xE11D,
+        KEY_KPCOMMA => 0x7E,
+        KEY_HANGEUL => 0x72,
+        KEY_HANJA => 0x71,
+        KEY_YEN => 0x7D,
+        KEY_LEFTMETA => 0xE05B,
+        KEY_RIGHTMETA => 0xE05C,
+        KEY_COMPOSE => 0xE05D,
+        KEY_STOP => 0xE068,
+        KEY_UNDO => 0xE008,
+        KEY_COPY => 0xE018,
+        KEY_PASTE => 0xE00A,
+        KEY_CUT => 0xE017,
+        KEY_HELP => 0xE03B,
+        KEY_CALC => 0xE021,
+        KEY_SLEEP => 0xE05F,
+        KEY_WAKEUP => 0xE063,
+        KEY_MAIL => 0xE06C,
+        KEY_BOOKMARKS => 0xE066,
+        KEY_COMPUTER => 0xE06B,
+        KEY_BACK => 0xE06A,
+        KEY_FORWARD => 0xE069,
+        KEY_NEXTSONG => 0xE019,
+        KEY_PLAYPAUSE => 0xE022,
+        KEY_PREVIOUSSONG => 0xE010,
+        KEY_STOPCD => 0xE024,
+        KEY_HOMEPAGE => 0xE032,
+        KEY_REFRESH => 0xE067,
+        KEY_F13 => 0x64,
+        KEY_F14 => 0x65,
+        KEY_F15 => 0x66,
+        KEY_F16 => 0x67,
+        KEY_F17 => 0x68,
+        KEY_F18 => 0x69,
+        KEY_F19 => 0x6A,
+        KEY_F20 => 0x6B,
+        KEY_F21 => 0x6C,
+        KEY_F22 => 0x6D,
+        KEY_F23 => 0x6E,
+        KEY_F24 => 0x76,
+        KEY_SEARCH => 0xE065,
+        KEY_MEDIA => 0xE06D,
+        _ => return None,
+    };
+    Some(ScanCode(scan_code))
+}
+
+pub(super) fn evdev_from_scan_code(scan_code: ScanCode) -> Option<u32> {
+    let code = match scan_code.0 {
+        0x01..=0x53 | 0x56..=0x58 => scan_code.0,
+        0x73 => KEY_RO,
+        0x78 => KEY_KATAKANA,
+        0x77 => KEY_HIRAGANA,
+        0x79 => KEY_HENKAN,
+        0x70 => KEY_KATAKANAHIRAGANA,
+        0x7B => KEY_MUHENKAN,
+        0x5C => KEY_KPJPCOMMA,
+        0xE01C => KEY_KPENTER,
+        0xE01D => KEY_RIGHTCTRL,
+        0xE035 => KEY_KPSLASH,
+        0xE037 => KEY_SYSRQ,
+        0xE038 => KEY_RIGHTALT,
+        0xE047 => KEY_HOME,
+        0xE048 => KEY_UP,
+        0xE049 => KEY_PAGEUP,
+        0xE04B => KEY_LEFT,
+        0xE04D => KEY_RIGHT,
+        0xE04F => KEY_END,
+        0xE050 => KEY_DOWN,
+        0xE051 => KEY_PAGEDOWN,
+        0xE052 => KEY_INSERT,
+        0xE053 => KEY_DELETE,
+        0xE020 => KEY_MUTE,
+        0xE02E => KEY_VOLUMEDOWN,
+        0xE030 => KEY_VOLUMEUP,
+        0xE05E => KEY_POWER,
+        0x59 => KEY_KPEQUAL,
+        0xE11D => KEY_PAUSE,
+        0x7E => KEY_KPCOMMA,
+        0x72 => KEY_HANGEUL,
+        0x71 => KEY_HANJA,
+        0x7D => KEY_YEN,
+        0xE05B => KEY_LEFTMETA,
+        0xE05C => KEY_RIGHTMETA,
+        0xE05D => KEY_COMPOSE,
+        0xE068 => KEY_STOP,
+        0xE008 => KEY_UNDO,
+        0xE018 => KEY_COPY,
+        0xE00A => KEY_PASTE,
+        0xE017 => KEY_CUT,
+        0xE03B => KEY_HELP,
+        0xE021 => KEY_CALC,
+        0xE05F => KEY_SLEEP,
+        0xE063 => KEY_WAKEUP,
+        0xE06C => KEY_MAIL,
+        0xE066 => KEY_BOOKMARKS,
+        0xE06B => KEY_COMPUTER,
+        0xE06A => KEY_BACK,
+        0xE069 => KEY_FORWARD,
+        0xE019 => KEY_NEXTSONG,
+        0xE022 => KEY_PLAYPAUSE,
+        0xE010 => KEY_PREVIOUSSONG,
+        0xE024 => KEY_STOPCD,
+        0xE032 => KEY_HOMEPAGE,
+        0xE067 => KEY_REFRESH,
+        0x64 => KEY_F13,
+        0x65 => KEY_F14,
+        0x66 => KEY_F15,
+        0x67 => KEY_F16,
+        0x68 => KEY_F17,
+        0x69 => KEY_F18,
+        0x6A => KEY_F19,
+        0x6B => KEY_F20,
+        0x6C => KEY_F21,
+        0x6D => KEY_F22,
+        0x6E => KEY_F23,
+        0x76 => KEY_F24,
+        0xE065 => KEY_SEARCH,
+        0xE06D => KEY_MEDIA,
+        _ => return None,
+    };
+    Some(code)
+}
+
+impl VirtualKey {
+    /// Codes without a variant give `Other(0)`, since `Other` holds Windows virtual-key codes.
+    #[inline]
+    pub fn from_evdev(code: u32) -> Self {
+        vkey_from_evdev(code).unwrap_or(VirtualKey::Other(0))
+    }
+
+    #[inline]
+    pub fn to_evdev(&self) -> Option<u32> {
+        evdev_from_vkey(*self)
+    }
+}
+
+impl ScanCode {
+    #[inline]
+    pub fn from_evdev(code: u32) -> Option<Self> {
+        scan_code_from_evdev(code)
+    }
+
+    #[inline]
+    pub fn to_evdev(&self) -> Option<u32> {
+        evdev_from_scan_code(*self)
+    }
+}
+
+impl KeyCode {
+    #[inline]
+    pub fn from_evdev(code: u32) -> Self {
+        Self {
+            vkey: VirtualKey::from_evdev(code),
+            scan_code: ScanCode::from_evdev(code).unwrap_or(ScanCode(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evdev_to_vkey() {
+        assert!(VirtualKey::from_evdev(KEY_A) == VirtualKey::A);
+        assert!(VirtualKey::from_evdev(KEY_KPENTER) == VirtualKey::NumEnter);
+        assert!(VirtualKey::from_evdev(KEY_RIGHTSHIFT) == VirtualKey::RShift);
+        assert!(VirtualKey::from_evdev(KEY_APOSTROPHE) == VirtualKey::Quote);
+        assert!(VirtualKey::from_evdev(KEY_102ND) == VirtualKey::IntlBackslash);
+        assert!(VirtualKey::from_evdev(KEY_F24) == VirtualKey::F24);
+        assert!(VirtualKey::from_evdev(KEY_MUTE) == VirtualKey::VolumeMute);
+        assert!(VirtualKey::from_evdev(KEY_HELP) == VirtualKey::Other(0));
+        assert!(VirtualKey::Other(KEY_HELP).to_evdev().is_none());
+    }
+
+    #[test]
+    fn vkey_evdev_round_trip() {
+        for code in 0..0x300 {
+            if let Some(vkey) = vkey_from_evdev(code) {
+                let code = vkey.to_evdev().unwrap();
+                assert!(VirtualKey::from_evdev(code) == vkey);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_code_evdev_round_trip() {
+        for code in 0..0x300 {
+            if let Some(scan_code) = ScanCode::from_evdev(code) {
+                assert!(scan_code.to_evdev() == Some(code));
+            }
+        }
+        assert!(ScanCode::from_evdev(KEY_Z) == Some(ScanCode(0x2C)));
+        assert!(ScanCode::from_evdev(KEY_RIGHTCTRL) == Some(ScanCode(0xE01D)));
+        assert!(ScanCode::from_evdev(KEY_PAUSE) == Some(ScanCode(0xE11D)));
+    }
+
+    #[test]
+    fn key_code_from_evdev() {
+        let key = KeyCode::from_evdev(KEY_UP);
+        assert!(key.vkey == VirtualKey::Up);
+        assert!(key.scan_code == ScanCode(0xE048));
+        let key = KeyCode::from(VirtualKey::NumDiv);
+        assert!(key.scan_code == ScanCode(0xE035));
+        let key = KeyCode::from(ScanCode(0x1E));
+        assert!(key.vkey == VirtualKey::A);
+    }
+}
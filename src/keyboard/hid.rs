@@ -0,0 +1,451 @@
+use super::evdev::*;
+use super::*;
+
+/// A usage ID on the HID Keyboard/Keypad page (0x07).
+///
+/// Usages are converted through their Linux evdev code, following the kernel's hid-input table.
+/// `0x9C` (Clear) and `0xD8` (Keypad Clear) map to Delete there, and so they do here. The
+/// usages that hid-input leaves unmapped are unmapped here too:
+///
+/// - `0x00..=0x03` report errors rather than keys.
+/// - `0x82..=0x84`, the locking Caps, Num and Scroll Lock keys, report a toggle state rather
+///   than presses.
+/// - `0x86` (Keypad Equal Sign for AS/400), `0x8D..=0x8F` (International7 to 9) and
+///   `0x95..=0x98` (LANG6 to 9) have no evdev code.
+/// - `0x99..=0xA4`, apart from `0x9C`, are legacy terminal keys such as SysReq/Attention and
+///   CrSel, which hid-input does not map although evdev has codes for a few of them.
+/// - `0xB0..=0xDD`, apart from `0xB6`, `0xB7` and `0xD8`, are the extended keypad keys such as
+///   Keypad 00, the hex digits and the memory keys, which have no evdev code.
+/// - `0xA5..=0xAF` and `0xDE..=0xDF` are reserved.
+///
+/// Of the mapped usages, `0x74` (Open), `0x76` (Props), `0x77` (Front), `0x79` (Again), `0x7E`
+/// (Find), `0x94` (Zenkaku/Hankaku), `0xB6` and `0xB7` (keypad parentheses) have no set 1 scan
+/// code. `0x32` (Non-US #) shares the scan code of `0x31` (backslash), and Clear shares the
+/// scan code of `0x4C` (Delete).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HidUsage(pub u16);
+
+impl HidUsage {
+    pub const PAGE: u16 = 0x07;
+}
+
+impl std::fmt::Display for HidUsage {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:02X}", self.0)
+    }
+}
+
+fn evdev_from_hid_usage(usage: HidUsage) -> Option<u32> {
+    let code = match usage.0 {
+        0x04 => KEY_A,
+        0x05 => KEY_B,
+        0x06 => KEY_C,
+        0x07 => KEY_D,
+        0x08 => KEY_E,
+        0x09 => KEY_F,
+        0x0A => KEY_G,
+        0x0B => KEY_H,
+        0x0C => KEY_I,
+        0x0D => KEY_J,
+        0x0E => KEY_K,
+        0x0F => KEY_L,
+        0x10 => KEY_M,
+        0x11 => KEY_N,
+        0x12 => KEY_O,
+        0x13 => KEY_P,
+        0x14 => KEY_Q,
+        0x15 => KEY_R,
+        0x16 => KEY_S,
+        0x17 => KEY_T,
+        0x18 => KEY_U,
+        0x19 => KEY_V,
+        0x1A => KEY_W,
+        0x1B => KEY_X,
+        0x1C => KEY_Y,
+        0x1D => KEY_Z,
+        0x1E => KEY_1,
+        0x1F => KEY_2,
+        0x20 => KEY_3,
+        0x21 => KEY_4,
+        0x22 => KEY_5,
+        0x23 => KEY_6,
+        0x24 => KEY_7,
+        0x25 => KEY_8,
+        0x26 => KEY_9,
+        0x27 => KEY_0,
+        0x28 => KEY_ENTER,
+        0x29 => KEY_ESC,
+        0x2A => KEY_BACKSPACE,
+        0x2B => KEY_TAB,
+        0x2C => KEY_SPACE,
+        0x2D => KEY_MINUS,
+        0x2E => KEY_EQUAL,
+        0x2F => KEY_LEFTBRACE,
+        0x30 => KEY_RIGHTBRACE,
+        0x31 => KEY_BACKSLASH,
+        0x32 => KEY_BACKSLASH,
+        0x33 => KEY_SEMICOLON,
+        0x34 => KEY_APOSTROPHE,
+        0x35 => KEY_GRAVE,
+        0x36 => KEY_COMMA,
+        0x37 => KEY_DOT,
+        0x38 => KEY_SLASH,
+        0x39 => KEY_CAPSLOCK,
+        0x3A => KEY_F1,
+        0x3B => KEY_F2,
+        0x3C => KEY_F3,
+        0x3D => KEY_F4,
+        0x3E => KEY_F5,
+        0x3F => KEY_F6,
+        0x40 => KEY_F7,
+        0x41 => KEY_F8,
+        0x42 => KEY_F9,
+        0x43 => KEY_F10,
+        0x44 => KEY_F11,
+        0x45 => KEY_F12,
+        0x46 => KEY_SYSRQ,
+        0x47 => KEY_SCROLLLOCK,
+        0x48 => KEY_PAUSE,
+        0x49 => KEY_INSERT,
+        0x4A => KEY_HOME,
+        0x4B => KEY_PAGEUP,
+        0x4C => KEY_DELETE,
+        0x4D => KEY_END,
+        0x4E => KEY_PAGEDOWN,
+        0x4F => KEY_RIGHT,
+        0x50 => KEY_LEFT,
+        0x51 => KEY_DOWN,
+        0x52 => KEY_UP,
+        0x53 => KEY_NUMLOCK,
+        0x54 => KEY_KPSLASH,
+        0x55 => KEY_KPASTERISK,
+        0x56 => KEY_KPMINUS,
+        0x57 => KEY_KPPLUS,
+        0x58 => KEY_KPENTER,
+        0x59 => KEY_KP1,
+        0x5A => KEY_KP2,
+        0x5B => KEY_KP3,
+        0x5C => KEY_KP4,
+        0x5D => KEY_KP5,
+        0x5E => KEY_KP6,
+        0x5F => KEY_KP7,
+        0x60 => KEY_KP8,
+        0x61 => KEY_KP9,
+        0x62 => KEY_KP0,
+        0x63 => KEY_KPDOT,
+        0x64 => KEY_102ND,
+        0x65 => KEY_COMPOSE,
+        0x66 => KEY_POWER,
+        0x67 => KEY_KPEQUAL,
+        0x68 => KEY_F13,
+        0x69 => KEY_F14,
+        0x6A => KEY_F15,
+        0x6B => KEY_F16,
+        0x6C => KEY_F17,
+        0x6D => KEY_F18,
+        0x6E => KEY_F19,
+        0x6F => KEY_F20,
+        0x70 => KEY_F21,
+        0x71 => KEY_F22,
+        0x72 => KEY_F23,
+        0x73 => KEY_F24,
+        0x74 => KEY_OPEN,
+        0x75 => KEY_HELP,
+        0x76 => KEY_PROPS,
+        0x77 => KEY_FRONT,
+        0x78 => KEY_STOP,
+        0x79 => KEY_AGAIN,
+        0x7A => KEY_UNDO,
+        0x7B => KEY_CUT,
+        0x7C => KEY_COPY,
+        0x7D => KEY_PASTE,
+        0x7E => KEY_FIND,
+        0x7F => KEY_MUTE,
+        0x80 => KEY_VOLUMEUP,
+        0x81 => KEY_VOLUMEDOWN,
+        0x85 => KEY_KPCOMMA,
+        0x87 => KEY_RO,
+        0x88 => KEY_KATAKANAHIRAGANA,
+        0x89 => KEY_YEN,
+        0x8A => KEY_HENKAN,
+        0x8B => KEY_MUHENKAN,
+        0x8C => KEY_KPJPCOMMA,
+        0x90 => KEY_HANGEUL,
+        0x91 => KEY_HANJA,
+        0x92 => KEY_KATAKANA,
+        0x93 => KEY_HIRAGANA,
+        0x94 => KEY_ZENKAKUHANKAKU,
+        0x9C => KEY_DELETE,
+        0xB6 => KEY_KPLEFTPAREN,
+        0xB7 => KEY_KPRIGHTPAREN,
+        0xD8 => KEY_DELETE,
+        0xE0 => KEY_LEFTCTRL,
+        0xE1 => KEY_LEFTSHIFT,
+        0xE2 => KEY_LEFTALT,
+        0xE3 => KEY_LEFTMETA,
+        0xE4 => KEY_RIGHTCTRL,
+        0xE5 => KEY_RIGHTSHIFT,
+        0xE6 => KEY_RIGHTALT,
+        0xE7 => KEY_RIGHTMETA,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn hid_usage_from_evdev(code: u32) -> Option<HidUsage> {
+    let usage = match code {
+        KEY_A => 0x04,
+        KEY_B => 0x05,
+        KEY_C => 0x06,
+        KEY_D => 0x07,
+        KEY_E => 0x08,
+        KEY_F => 0x09,
+        KEY_G => 0x0A,
+        KEY_H => 0x0B,
+        KEY_I => 0x0C,
+        KEY_J => 0x0D,
+        KEY_K => 0x0E,
+        KEY_L => 0x0F,
+        KEY_M => 0x10,
+        KEY_N => 0x11,
+        KEY_O => 0x12,
+        KEY_P => 0x13,
+        KEY_Q => 0x14,
+        KEY_R => 0x15,
+        KEY_S => 0x16,
+        KEY_T => 0x17,
+        KEY_U => 0x18,
+        KEY_V => 0x19,
+        KEY_W => 0x1A,
+        KEY_X => 0x1B,
+        KEY_Y => 0x1C,
+        KEY_Z => 0x1D,
+        KEY_1 => 0x1E,
+        KEY_2 => 0x1F,
+        KEY_3 => 0x20,
+        KEY_4 => 0x21,
+        KEY_5 => 0x22,
+        KEY_6 => 0x23,
+        KEY_7 => 0x24,
+        KEY_8 => 0x25,
+        KEY_9 => 0x26,
+        KEY_0 => 0x27,
+        KEY_ENTER => 0x28,
+        KEY_ESC => 0x29,
+        KEY_BACKSPACE => 0x2A,
+        KEY_TAB => 0x2B,
+        KEY_SPACE => 0x2C,
+        KEY_MINUS => 0x2D,
+        KEY_EQUAL => 0x2E,
+        KEY_LEFTBRACE => 0x2F,
+        KEY_RIGHTBRACE => 0x30,
+        KEY_BACKSLASH => 0x31,
+        KEY_SEMICOLON => 0x33,
+        KEY_APOSTROPHE => 0x34,
+        KEY_GRAVE => 0x35,
+        KEY_COMMA => 0x36,
+        KEY_DOT => 0x37,
+        KEY_SLASH => 0x38,
+        KEY_CAPSLOCK => 0x39,
+        KEY_F1 => 0x3A,
+        KEY_F2 => 0x3B,
+        KEY_F3 => 0x3C,
+        KEY_F4 => 0x3D,
+        KEY_F5 => 0x3E,
+        KEY_F6 => 0x3F,
+        KEY_F7 => 0x40,
+        KEY_F8 => 0x41,
+        KEY_F9 => 0x42,
+        KEY_F10 => 0x43,
+        KEY_F11 => 0x44,
+        KEY_F12 => 0x45,
+        KEY_SYSRQ => 0x46,
+        KEY_SCROLLLOCK => 0x47,
+        KEY_PAUSE => 0x48,
+        KEY_INSERT => 0x49,
+        KEY_HOME => 0x4A,
+        KEY_PAGEUP => 0x4B,
+        KEY_DELETE => 0x4C,
+        KEY_END => 0x4D,
+        KEY_PAGEDOWN => 0x4E,
+        KEY_RIGHT => 0x4F,
+        KEY_LEFT => 0x50,
+        KEY_DOWN => 0x51,
+        KEY_UP => 0x52,
+        KEY_NUMLOCK => 0x53,
+        KEY_KPSLASH => 0x54,
+        KEY_KPASTERISK => 0x55,
+        KEY_KPMINUS => 0x56,
+        KEY_KPPLUS => 0x57,
+        KEY_KPENTER => 0x58,
+        KEY_KP1 => 0x59,
+        KEY_KP2 => 0x5A,
+        KEY_KP3 => 0x5B,
+        KEY_KP4 => 0x5C,
+        KEY_KP5 => 0x5D,
+        KEY_KP6 => 0x5E,
+        KEY_KP7 => 0x5F,
+        KEY_KP8 => 0x60,
+        KEY_KP9 => 0x61,
+        KEY_KP0 => 0x62,
+        KEY_KPDOT => 0x63,
+        KEY_102ND => 0x64,
+        KEY_COMPOSE => 0x65,
+        KEY_POWER => 0x66,
+        KEY_KPEQUAL => 0x67,
+        KEY_F13 => 0x68,
+        KEY_F14 => 0x69,
+        KEY_F15 => 0x6A,
+        KEY_F16 => 0x6B,
+        KEY_F17 => 0x6C,
+        KEY_F18 => 0x6D,
+        KEY_F19 => 0x6E,
+        KEY_F20 => 0x6F,
+        KEY_F21 => 0x70,
+        KEY_F22 => 0x71,
+        KEY_F23 => 0x72,
+        KEY_F24 => 0x73,
+        KEY_OPEN => 0x74,
+        KEY_HELP => 0x75,
+        KEY_PROPS => 0x76,
+        KEY_FRONT => 0x77,
+        KEY_STOP => 0x78,
+        KEY_AGAIN => 0x79,
+        KEY_UNDO => 0x7A,
+        KEY_CUT => 0x7B,
+        KEY_COPY => 0x7C,
+        KEY_PASTE => 0x7D,
+        KEY_FIND => 0x7E,
+        KEY_MUTE => 0x7F,
+        KEY_VOLUMEUP => 0x80,
+        KEY_VOLUMEDOWN => 0x81,
+        KEY_KPCOMMA => 0x85,
+        KEY_RO => 0x87,
+        KEY_KATAKANAHIRAGANA => 0x88,
+        KEY_YEN => 0x89,
+        KEY_HENKAN => 0x8A,
+        KEY_MUHENKAN => 0x8B,
+        KEY_KPJPCOMMA => 0x8C,
+        KEY_HANGEUL => 0x90,
+        KEY_HANJA => 0x91,
+        KEY_KATAKANA => 0x92,
+        KEY_HIRAGANA => 0x93,
+        KEY_ZENKAKUHANKAKU => 0x94,
+        KEY_KPLEFTPAREN => 0xB6,
+        KEY_KPRIGHTPAREN => 0xB7,
+        KEY_LEFTCTRL => 0xE0,
+        KEY_LEFTSHIFT => 0xE1,
+        KEY_LEFTALT => 0xE2,
+        KEY_LEFTMETA => 0xE3,
+        KEY_RIGHTCTRL => 0xE4,
+        KEY_RIGHTSHIFT => 0xE5,
+        KEY_RIGHTALT => 0xE6,
+        KEY_RIGHTMETA => 0xE7,
+        _ => return None,
+    };
+    Some(HidUsage(usage))
+}
+
+impl VirtualKey {
+    #[inline]
+    pub fn from_hid_usage(usage: HidUsage) -> Option<Self> {
+        evdev_from_hid_usage(usage).and_then(vkey_from_evdev)
+    }
+
+    #[inline]
+    pub fn to_hid_usage(&self) -> Option<HidUsage> {
+        evdev_from_vkey(*self).and_then(hid_usage_from_evdev)
+    }
+}
+
+impl ScanCode {
+    #[inline]
+    pub fn from_hid_usage(usage: HidUsage) -> Option<Self> {
+        evdev_from_hid_usage(usage).and_then(ScanCode::from_evdev)
+    }
+
+    #[inline]
+    pub fn to_hid_usage(&self) -> Option<HidUsage> {
+        self.to_evdev().and_then(hid_usage_from_evdev)
+    }
+}
+
+impl KeyCode {
+    #[inline]
+    pub fn from_hid_usage(usage: HidUsage) -> Option<Self> {
+        evdev_from_hid_usage(usage).map(KeyCode::from_evdev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hid_usage_to_scan_code() {
+        assert!(ScanCode::from_hid_usage(HidUsage(0x04)) == Some(ScanCode(0x1E)));
+        assert!(ScanCode::from_hid_usage(HidUsage(0x28)) == Some(ScanCode(0x1C)));
+        assert!(ScanCode::from_hid_usage(HidUsage(0x58)) == Some(ScanCode(0xE01C)));
+        assert!(ScanCode::from_hid_usage(HidUsage(0x48)) == Some(ScanCode(0xE11D)));
+        assert!(ScanCode::from_hid_usage(HidUsage(0xE7)) == Some(ScanCode(0xE05C)));
+        assert!(ScanCode::from_hid_usage(HidUsage(0x01)).is_none());
+    }
+
+    fn is_unmapped(usage: u16) -> bool {
+        matches!(
+            usage,
+            0x00..=0x03
+                | 0x82..=0x84
+                | 0x86
+                | 0x8D..=0x8F
+                | 0x95..=0x9B
+                | 0x9D..=0xB5
+                | 0xB8..=0xD7
+                | 0xD9..=0xDF
+        )
+    }
+
+    fn has_no_scan_code(usage: u16) -> bool {
+        matches!(usage, 0x74 | 0x76 | 0x77 | 0x79 | 0x7E | 0x94 | 0xB6 | 0xB7)
+    }
+
+    #[test]
+    fn hid_usage_unmapped() {
+        for usage in 0x00..=0xE7 {
+            let key = KeyCode::from_hid_usage(HidUsage(usage));
+            assert!(key.is_none() == is_unmapped(usage), "{}", HidUsage(usage));
+        }
+    }
+
+    #[test]
+    fn hid_usage_scan_code_round_trip() {
+        for usage in 0x00..=0xE7 {
+            let scan_code = ScanCode::from_hid_usage(HidUsage(usage));
+            if is_unmapped(usage) || has_no_scan_code(usage) {
+                assert!(scan_code.is_none(), "{}", HidUsage(usage));
+                continue;
+            }
+            // Non-US # shares set 1 code 0x2B with the backslash key, and Clear 0xE053 with Delete.
+            let expected = match usage {
+                0x32 => 0x31,
+                0x9C | 0xD8 => 0x4C,
+                _ => usage,
+            };
+            let usage = scan_code.and_then(|s| s.to_hid_usage());
+            assert!(usage == Some(HidUsage(expected)), "{}", HidUsage(expected));
+        }
+    }
+
+    #[test]
+    fn hid_usage_to_vkey() {
+        assert!(VirtualKey::from_hid_usage(HidUsage(0x1D)) == Some(VirtualKey::Z));
+        assert!(VirtualKey::from_hid_usage(HidUsage(0x62)) == Some(VirtualKey::Num0));
+        assert!(VirtualKey::F24.to_hid_usage() == Some(HidUsage(0x73)));
+        assert!(VirtualKey::Other(0x04).to_hid_usage().is_none());
+        let key = KeyCode::from_hid_usage(HidUsage(0x52)).unwrap();
+        assert!(key.vkey == VirtualKey::Up);
+        assert!(key.scan_code == ScanCode(0xE048));
+    }
+}
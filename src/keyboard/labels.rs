@@ -0,0 +1,361 @@
+use super::names::CHAR_KEYS;
+use super::shortcut::MAC_MODIFIER_SYMBOLS;
+use super::*;
+use std::collections::HashMap;
+
+/// Human-facing labels for keys and modifiers, e.g. for shortcut hints in menus.
+///
+/// The built-in tables fall back to English labels such as `"Page Up"` or `"Num +"`, and keys
+/// and modifiers without any entry to their canonical names. Some labels depend on the style
+/// and take precedence over the table: `ShortcutStyle::Mac` uses symbols such as `⌫` and `⌘`,
+/// and `Windows` and `Linux` name the Meta keys `Win` and `Super`.
+#[derive(Clone, Default, Debug)]
+pub struct KeyLabels {
+    keys: HashMap<VirtualKey, String>,
+    modifiers: HashMap<Modifier, String>,
+}
+
+impl KeyLabels {
+    /// An empty table that labels keys with their canonical names, as `Shortcut::format` does.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The English table that every built-in locale falls back to.
+    #[inline]
+    pub fn english() -> Self {
+        let mut labels = Self::new();
+        labels.extend(&EN_KEYS, &EN_MODIFIERS);
+        labels
+    }
+
+    /// The built-in table for a locale such as `"de"` or `"fr-CA"`, on top of the English one.
+    /// Unknown locales give English.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let mut labels = Self::english();
+        match language.as_str() {
+            "de" => labels.extend(&DE_KEYS, &DE_MODIFIERS),
+            "fr" => labels.extend(&FR_KEYS, &FR_MODIFIERS),
+            "es" => labels.extend(&ES_KEYS, &ES_MODIFIERS),
+            "it" => labels.extend(&IT_KEYS, &IT_MODIFIERS),
+            _ => {}
+        }
+        labels
+    }
+
+    fn extend(&mut self, keys: &[(VirtualKey, &str)], modifiers: &[(Modifier, &str)]) {
+        self.keys
+            .extend(keys.iter().map(|(k, s)| (*k, s.to_string())));
+        self.modifiers
+            .extend(modifiers.iter().map(|(m, s)| (*m, s.to_string())));
+    }
+
+    #[inline]
+    pub fn set_key(&mut self, vkey: VirtualKey, label: impl Into<String>) {
+        self.keys.insert(vkey, label.into());
+    }
+
+    #[inline]
+    pub fn set_modifier(&mut self, modifier: Modifier, label: impl Into<String>) {
+        self.modifiers.insert(modifier, label.into());
+    }
+
+    pub fn key(&self, vkey: VirtualKey, style: ShortcutStyle) -> String {
+        if let Some(label) = style_key_label(vkey, style) {
+            return label.to_string();
+        }
+        if let Some(label) = self.keys.get(&vkey) {
+            return label.clone();
+        }
+        if let Some((c, _)) = CHAR_KEYS.iter().find(|(_, k)| *k == vkey) {
+            return c.to_string();
+        }
+        vkey.to_string()
+    }
+
+    #[inline]
+    pub fn key_code(&self, key: KeyCode, style: ShortcutStyle) -> String {
+        self.key(key.vkey, style)
+    }
+
+    /// Sided modifiers without their own entry use the label of the generic one.
+    pub fn modifier(&self, modifier: Modifier, style: ShortcutStyle) -> String {
+        if let Some(label) = style_modifier_label(modifier, style) {
+            return label;
+        }
+        self.modifiers
+            .get(&modifier)
+            .or_else(|| self.modifiers.get(&modifier.generic()))
+            .cloned()
+            .unwrap_or_else(|| modifier.to_string())
+    }
+
+    /// Formats like `Shortcut::format` with the labels of this table.
+    pub fn shortcut(&self, shortcut: &Shortcut, style: ShortcutStyle) -> String {
+        let mut s = String::new();
+        match style {
+            ShortcutStyle::Mac => {
+                for (m, symbol) in MAC_MODIFIER_SYMBOLS {
+                    if shortcut.modifiers.contains(m) {
+                        s.push(symbol);
+                    }
+                }
+            }
+            _ => {
+                for m in shortcut.modifiers.without_sides().without_locks() {
+                    s.push_str(&self.modifier(m, style));
+                    s.push('+');
+                }
+            }
+        }
+        s.push_str(&self.key(shortcut.key, style));
+        s
+    }
+}
+
+fn style_key_label(vkey: VirtualKey, style: ShortcutStyle) -> Option<&'static str> {
+    let label = match (style, vkey) {
+        (ShortcutStyle::Mac, _) => return mac_key_symbol(vkey),
+        (ShortcutStyle::Windows, VirtualKey::LMeta) => "Left Win",
+        (ShortcutStyle::Windows, VirtualKey::RMeta) => "Right Win",
+        (ShortcutStyle::Linux, VirtualKey::LMeta) => "Left Super",
+        (ShortcutStyle::Linux, VirtualKey::RMeta) => "Right Super",
+        _ => return None,
+    };
+    Some(label)
+}
+
+fn style_modifier_label(modifier: Modifier, style: ShortcutStyle) -> Option<String> {
+    let label = match (style, modifier.generic()) {
+        (ShortcutStyle::Mac, generic) => {
+            let (_, symbol) = MAC_MODIFIER_SYMBOLS.iter().find(|(m, _)| *m == generic)?;
+            return Some(symbol.to_string());
+        }
+        (ShortcutStyle::Windows, Modifier::Meta) => "Win",
+        (ShortcutStyle::Linux, Modifier::Meta) => "Super",
+        _ => return None,
+    };
+    Some(label.to_string())
+}
+
+fn mac_key_symbol(vkey: VirtualKey) -> Option<&'static str> {
+    let symbol = match vkey {
+        VirtualKey::Enter => "↩",
+        VirtualKey::NumEnter => "⌤",
+        VirtualKey::BackSpace => "⌫",
+        VirtualKey::Delete => "⌦",
+        VirtualKey::Esc => "⎋",
+        VirtualKey::Tab => "⇥",
+        VirtualKey::CapsLock => "⇪",
+        VirtualKey::Up => "↑",
+        VirtualKey::Down => "↓",
+        VirtualKey::Left => "←",
+        VirtualKey::Right => "→",
+        VirtualKey::PageUp => "⇞",
+        VirtualKey::PageDown => "⇟",
+        VirtualKey::Home => "↖",
+        VirtualKey::End => "↘",
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+const EN_MODIFIERS: [(Modifier, &str); 6] = [
+    (Modifier::Ctrl, "Ctrl"),
+    (Modifier::Shift, "Shift"),
+    (Modifier::Alt, "Alt"),
+    (Modifier::Meta, "Meta"),
+    (Modifier::CapsLock, "Caps Lock"),
+    (Modifier::NumLock, "Num Lock"),
+];
+
+const EN_KEYS: [(VirtualKey, &str); 52] = [
+    (VirtualKey::Esc, "Esc"),
+    (VirtualKey::CapsLock, "Caps Lock"),
+    (VirtualKey::LShift, "Left Shift"),
+    (VirtualKey::RShift, "Right Shift"),
+    (VirtualKey::LCtrl, "Left Ctrl"),
+    (VirtualKey::RCtrl, "Right Ctrl"),
+    (VirtualKey::LAlt, "Left Alt"),
+    (VirtualKey::RAlt, "Right Alt"),
+    (VirtualKey::LMeta, "Left Meta"),
+    (VirtualKey::RMeta, "Right Meta"),
+    (VirtualKey::ContextMenu, "Menu"),
+    (VirtualKey::BackSpace, "Backspace"),
+    (VirtualKey::PrintScreen, "Print Screen"),
+    (VirtualKey::ScrollLock, "Scroll Lock"),
+    (VirtualKey::PageUp, "Page Up"),
+    (VirtualKey::PageDown, "Page Down"),
+    (VirtualKey::IntlBackslash, "Intl \\"),
+    (VirtualKey::NumLock, "Num Lock"),
+    (VirtualKey::Num1, "Num 1"),
+    (VirtualKey::Num2, "Num 2"),
+    (VirtualKey::Num3, "Num 3"),
+    (VirtualKey::Num4, "Num 4"),
+    (VirtualKey::Num5, "Num 5"),
+    (VirtualKey::Num6, "Num 6"),
+    (VirtualKey::Num7, "Num 7"),
+    (VirtualKey::Num8, "Num 8"),
+    (VirtualKey::Num9, "Num 9"),
+    (VirtualKey::Num0, "Num 0"),
+    (VirtualKey::NumAdd, "Num +"),
+    (VirtualKey::NumSub, "Num -"),
+    (VirtualKey::NumMul, "Num *"),
+    (VirtualKey::NumDiv, "Num /"),
+    (VirtualKey::NumDecimal, "Num ."),
+    (VirtualKey::NumEnter, "Num Enter"),
+    (VirtualKey::VolumeMute, "Mute"),
+    (VirtualKey::VolumeDown, "Volume Down"),
+    (VirtualKey::VolumeUp, "Volume Up"),
+    (VirtualKey::MediaNextTrack, "Next Track"),
+    (VirtualKey::MediaPrevTrack, "Previous Track"),
+    (VirtualKey::MediaStop, "Stop"),
+    (VirtualKey::MediaPlayPause, "Play/Pause"),
+    (VirtualKey::BrowserBack, "Back"),
+    (VirtualKey::BrowserForward, "Forward"),
+    (VirtualKey::BrowserRefresh, "Refresh"),
+    (VirtualKey::BrowserStop, "Browser Stop"),
+    (VirtualKey::BrowserSearch, "Search"),
+    (VirtualKey::BrowserFavorites, "Favorites"),
+    (VirtualKey::BrowserHome, "Browser Home"),
+    (VirtualKey::LaunchMail, "Mail"),
+    (VirtualKey::LaunchMediaSelect, "Media"),
+    (VirtualKey::LaunchApp1, "App 1"),
+    (VirtualKey::LaunchApp2, "App 2"),
+];
+
+const DE_MODIFIERS: [(Modifier, &str); 2] =
+    [(Modifier::Ctrl, "Strg"), (Modifier::Shift, "Umschalt")];
+
+const DE_KEYS: [(VirtualKey, &str); 14] = [
+    (VirtualKey::Esc, "Esc"),
+    (VirtualKey::Enter, "Eingabe"),
+    (VirtualKey::BackSpace, "Rücktaste"),
+    (VirtualKey::Space, "Leertaste"),
+    (VirtualKey::CapsLock, "Feststell"),
+    (VirtualKey::Insert, "Einfg"),
+    (VirtualKey::Delete, "Entf"),
+    (VirtualKey::Home, "Pos1"),
+    (VirtualKey::End, "Ende"),
+    (VirtualKey::PageUp, "Bild↑"),
+    (VirtualKey::PageDown, "Bild↓"),
+    (VirtualKey::PrintScreen, "Druck"),
+    (VirtualKey::ScrollLock, "Rollen"),
+    (VirtualKey::Pause, "Pause"),
+];
+
+const FR_MODIFIERS: [(Modifier, &str); 1] = [(Modifier::Shift, "Maj")];
+
+const FR_KEYS: [(VirtualKey, &str); 13] = [
+    (VirtualKey::Esc, "Échap"),
+    (VirtualKey::Enter, "Entrée"),
+    (VirtualKey::BackSpace, "Retour arrière"),
+    (VirtualKey::Space, "Espace"),
+    (VirtualKey::CapsLock, "Verr. maj"),
+    (VirtualKey::Insert, "Inser"),
+    (VirtualKey::Delete, "Suppr"),
+    (VirtualKey::Home, "Origine"),
+    (VirtualKey::End, "Fin"),
+    (VirtualKey::PageUp, "Pg préc"),
+    (VirtualKey::PageDown, "Pg suiv"),
+    (VirtualKey::PrintScreen, "Impr écran"),
+    (VirtualKey::ScrollLock, "Arrêt défil"),
+];
+
+const ES_MODIFIERS: [(Modifier, &str); 1] = [(Modifier::Shift, "Mayús")];
+
+const ES_KEYS: [(VirtualKey, &str); 13] = [
+    (VirtualKey::Esc, "Esc"),
+    (VirtualKey::Enter, "Intro"),
+    (VirtualKey::BackSpace, "Retroceso"),
+    (VirtualKey::Space, "Espacio"),
+    (VirtualKey::CapsLock, "Bloq Mayús"),
+    (VirtualKey::Insert, "Insert"),
+    (VirtualKey::Delete, "Supr"),
+    (VirtualKey::Home, "Inicio"),
+    (VirtualKey::End, "Fin"),
+    (VirtualKey::PageUp, "RePág"),
+    (VirtualKey::PageDown, "AvPág"),
+    (VirtualKey::PrintScreen, "Impr Pant"),
+    (VirtualKey::ScrollLock, "Bloq Despl"),
+];
+
+const IT_MODIFIERS: [(Modifier, &str); 1] = [(Modifier::Shift, "Maiusc")];
+
+const IT_KEYS: [(VirtualKey, &str); 12] = [
+    (VirtualKey::Esc, "Esc"),
+    (VirtualKey::Enter, "Invio"),
+    (VirtualKey::BackSpace, "Backspace"),
+    (VirtualKey::Space, "Spazio"),
+    (VirtualKey::CapsLock, "Bloc Maiusc"),
+    (VirtualKey::Insert, "Ins"),
+    (VirtualKey::Delete, "Canc"),
+    (VirtualKey::Home, "Home"),
+    (VirtualKey::End, "Fine"),
+    (VirtualKey::PageUp, "Pag su"),
+    (VirtualKey::PageDown, "Pag giù"),
+    (VirtualKey::PrintScreen, "Stamp"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_labels() {
+        let de = KeyLabels::for_locale("de-DE");
+        assert!(de.key(VirtualKey::Delete, ShortcutStyle::Standard) == "Entf");
+        assert!(de.key(VirtualKey::Delete, ShortcutStyle::Mac) == "⌦");
+        assert!(de.key(VirtualKey::F5, ShortcutStyle::Standard) == "F5");
+        assert!(de.modifier(Modifier::LCtrl, ShortcutStyle::Standard) == "Strg");
+        let s = Shortcut::new(Modifier::Ctrl | Modifier::Shift, VirtualKey::BackSpace);
+        assert!(de.shortcut(&s, ShortcutStyle::Standard) == "Strg+Umschalt+Rücktaste");
+        assert!(de.shortcut(&s, ShortcutStyle::Mac) == "⌃⇧⌫");
+        let en = KeyLabels::for_locale("xx");
+        assert!(en.shortcut(&s, ShortcutStyle::Standard) == "Ctrl+Shift+Backspace");
+        assert!(en.key(VirtualKey::Key1, ShortcutStyle::Standard) == "1");
+        assert!(en.key(VirtualKey::NumAdd, ShortcutStyle::Standard) == "Num +");
+        assert!(en.key(VirtualKey::F5, ShortcutStyle::Standard) == "F5");
+        assert!(de.key(VirtualKey::PageUp, ShortcutStyle::Standard) == "Bild↑");
+        assert!(de.key(VirtualKey::NumLock, ShortcutStyle::Standard) == "Num Lock");
+        assert!(de.modifier(Modifier::Alt, ShortcutStyle::Standard) == "Alt");
+        let s = Shortcut::new(Modifier::Ctrl, VirtualKey::BackSpace);
+        assert!(KeyLabels::new().shortcut(&s, ShortcutStyle::Standard) == "Ctrl+BackSpace");
+    }
+
+    #[test]
+    fn key_labels_meta() {
+        let en = KeyLabels::english();
+        assert!(en.modifier(Modifier::RMeta, ShortcutStyle::Standard) == "Meta");
+        assert!(en.modifier(Modifier::RMeta, ShortcutStyle::Windows) == "Win");
+        assert!(en.modifier(Modifier::RMeta, ShortcutStyle::Linux) == "Super");
+        assert!(en.modifier(Modifier::RMeta, ShortcutStyle::Mac) == "⌘");
+        assert!(en.key(VirtualKey::LMeta, ShortcutStyle::Standard) == "Left Meta");
+        assert!(en.key(VirtualKey::LMeta, ShortcutStyle::Windows) == "Left Win");
+        assert!(en.key(VirtualKey::RMeta, ShortcutStyle::Linux) == "Right Super");
+        let s = Shortcut::new(Modifier::Ctrl | Modifier::Meta, VirtualKey::BackSpace);
+        assert!(en.shortcut(&s, ShortcutStyle::Windows) == "Ctrl+Win+Backspace");
+        assert!(en.shortcut(&s, ShortcutStyle::Linux) == "Ctrl+Super+Backspace");
+        let de = KeyLabels::for_locale("de");
+        assert!(de.shortcut(&s, ShortcutStyle::Windows) == "Strg+Win+Rücktaste");
+        assert!(s.format(ShortcutStyle::Windows) == "Ctrl+Win+BackSpace");
+        assert!(s.format(ShortcutStyle::Linux).parse::<Shortcut>() == Ok(s));
+    }
+
+    #[test]
+    fn custom_key_labels() {
+        let mut labels = KeyLabels::for_locale("fr");
+        labels.set_key(VirtualKey::Enter, "Valider");
+        labels.set_modifier(Modifier::Ctrl, "Contrôle");
+        let s = Shortcut::new(Modifier::Ctrl | Modifier::Shift, VirtualKey::Enter);
+        assert!(labels.shortcut(&s, ShortcutStyle::Standard) == "Contrôle+Maj+Valider");
+        let key = KeyCode::new(VirtualKey::Delete, ScanCode(0xe053));
+        assert!(labels.key_code(key, ShortcutStyle::Standard) == "Suppr");
+    }
+}
@@ -0,0 +1,540 @@
+use super::*;
+
+/// What a key produces in a `KeyboardLayout`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum KeySymbol {
+    Char(char),
+    /// A dead key, holding the combining mark it applies, e.g. `'\u{302}'` for `^`.
+    Dead(char),
+}
+
+/// Maps `ScanCode`s of the main key block to characters.
+///
+/// Each entry lists the characters for no modifier, Shift, AltGr and Shift+AltGr. `'\0'` or a
+/// missing char means the level produces nothing, and a combining mark (U+0300 to U+036F) is a
+/// dead key.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyboardLayout {
+    name: &'static str,
+    keys: &'static [(u32, &'static str)],
+}
+
+impl KeyboardLayout {
+    pub const US: Self = Self::new("US", &US_KEYS);
+    pub const UK: Self = Self::new("UK", &UK_KEYS);
+    pub const DE: Self = Self::new("DE", &DE_KEYS);
+    pub const FR: Self = Self::new("FR", &FR_KEYS);
+    pub const JIS: Self = Self::new("JIS", &JIS_KEYS);
+
+    #[inline]
+    pub const fn new(name: &'static str, keys: &'static [(u32, &'static str)]) -> Self {
+        Self { name, keys }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// AltGr is `RAlt`, or `Ctrl` with `Alt` as on Windows. Other `Ctrl`, `Alt` and `Meta`
+    /// combinations produce nothing.
+    pub fn symbol(&self, scan_code: ScanCode, modifiers: Modifiers) -> Option<KeySymbol> {
+        let (_, levels) = self.keys.iter().find(|(sc, _)| *sc == scan_code.0)?;
+        let alt_gr = modifiers.contains(Modifier::RAlt)
+            || (modifiers.contains(Modifier::Ctrl) && modifiers.contains(Modifier::Alt));
+        if modifiers.contains(Modifier::Meta)
+            || (!alt_gr
+                && (modifiers.contains(Modifier::Ctrl) || modifiers.contains(Modifier::Alt)))
+        {
+            return None;
+        }
+        let mut shift = modifiers.contains(Modifier::Shift);
+        if modifiers.contains(Modifier::CapsLock) && !alt_gr {
+            let mut chars = levels.chars();
+            if let (Some(lower), Some(upper)) = (chars.next(), chars.next())
+                && lower.is_alphabetic()
+                && lower.to_uppercase().eq(std::iter::once(upper))
+            {
+                shift = !shift;
+            }
+        }
+        let level = (alt_gr as usize) * 2 + shift as usize;
+        match levels.chars().nth(level)? {
+            '\0' => None,
+            c if ('\u{300}'..='\u{36f}').contains(&c) => Some(KeySymbol::Dead(c)),
+            c => Some(KeySymbol::Char(c)),
+        }
+    }
+
+    /// Finds the key presses that type `c`, a dead key followed by a base character for
+    /// composed characters, or by Space for the accent alone. Modifiers use `LShift` and `RAlt`
+    /// for AltGr.
+    pub fn keystrokes(&self, c: char) -> Option<Vec<(ScanCode, Modifiers)>> {
+        if let Some(stroke) = self.find(c) {
+            return Some(vec![stroke]);
+        }
+        COMPOSE.iter().find_map(|(mark, bases, composed)| {
+            let base = if spacing_accent(*mark) == c {
+                ' '
+            } else {
+                let index = composed.chars().position(|x| x == c)?;
+                bases.chars().nth(index)?
+            };
+            Some(vec![self.find(*mark)?, self.find(base)?])
+        })
+    }
+
+    fn find(&self, c: char) -> Option<(ScanCode, Modifiers)> {
+        self.keys.iter().find_map(|(sc, levels)| {
+            let level = levels.chars().position(|x| x == c)?;
+            let mut modifiers = Modifiers::new();
+            if level & 1 != 0 {
+                modifiers.insert(Modifier::LShift);
+            }
+            if level & 2 != 0 {
+                modifiers.insert(Modifier::RAlt);
+            }
+            Some((ScanCode(*sc), modifiers))
+        })
+    }
+
+    /// Returns the text a key press produces, composing dead keys through `composer`.
+    #[inline]
+    pub fn text(
+        &self,
+        scan_code: ScanCode,
+        modifiers: Modifiers,
+        composer: &mut DeadKeyComposer,
+    ) -> Option<String> {
+        composer.compose(self.symbol(scan_code, modifiers)?)
+    }
+}
+
+/// Combines a dead key with the following character.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct DeadKeyComposer {
+    pending: Option<char>,
+}
+
+impl DeadKeyComposer {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The combining mark of a dead key waiting for the next character.
+    #[inline]
+    pub fn pending(&self) -> Option<char> {
+        self.pending
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    /// A dead key followed by a character without a composed form, or by a different dead key,
+    /// gives the accent followed by that character. Space or the same dead key gives the accent
+    /// alone.
+    pub fn compose(&mut self, symbol: KeySymbol) -> Option<String> {
+        match (self.pending.take(), symbol) {
+            (None, KeySymbol::Dead(mark)) => {
+                self.pending = Some(mark);
+                None
+            }
+            (None, KeySymbol::Char(c)) => Some(c.to_string()),
+            (Some(pending), KeySymbol::Dead(mark)) => {
+                if pending != mark {
+                    self.pending = Some(mark);
+                }
+                Some(spacing_accent(pending).to_string())
+            }
+            (Some(pending), KeySymbol::Char(' ')) => Some(spacing_accent(pending).to_string()),
+            (Some(pending), KeySymbol::Char(c)) => match compose_char(pending, c) {
+                Some(composed) => Some(composed.to_string()),
+                None => Some([spacing_accent(pending), c].iter().collect()),
+            },
+        }
+    }
+}
+
+fn spacing_accent(mark: char) -> char {
+    match mark {
+        '\u{300}' => '`',
+        '\u{301}' => '´',
+        '\u{302}' => '^',
+        '\u{303}' => '~',
+        '\u{308}' => '¨',
+        _ => mark,
+    }
+}
+
+fn compose_char(mark: char, c: char) -> Option<char> {
+    let (_, bases, composed) = COMPOSE.iter().find(|(m, _, _)| *m == mark)?;
+    let index = bases.chars().position(|b| b == c)?;
+    composed.chars().nth(index)
+}
+
+const COMPOSE: [(char, &str, &str); 5] = [
+    ('\u{300}', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('\u{301}', "aeiouyAEIOUY", "áéíóúýÁÉÍÓÚÝ"),
+    ('\u{302}', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+    ('\u{303}', "anoANO", "ãñõÃÑÕ"),
+    ('\u{308}', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+];
+
+const US_KEYS: [(u32, &str); 48] = [
+    (0x29, "`~"),
+    (0x02, "1!"),
+    (0x03, "2@"),
+    (0x04, "3#"),
+    (0x05, "4$"),
+    (0x06, "5%"),
+    (0x07, "6^"),
+    (0x08, "7&"),
+    (0x09, "8*"),
+    (0x0a, "9("),
+    (0x0b, "0)"),
+    (0x0c, "-_"),
+    (0x0d, "=+"),
+    (0x10, "qQ"),
+    (0x11, "wW"),
+    (0x12, "eE"),
+    (0x13, "rR"),
+    (0x14, "tT"),
+    (0x15, "yY"),
+    (0x16, "uU"),
+    (0x17, "iI"),
+    (0x18, "oO"),
+    (0x19, "pP"),
+    (0x1a, "[{"),
+    (0x1b, "]}"),
+    (0x2b, "\\|"),
+    (0x1e, "aA"),
+    (0x1f, "sS"),
+    (0x20, "dD"),
+    (0x21, "fF"),
+    (0x22, "gG"),
+    (0x23, "hH"),
+    (0x24, "jJ"),
+    (0x25, "kK"),
+    (0x26, "lL"),
+    (0x27, ";:"),
+    (0x28, "'\""),
+    (0x2c, "zZ"),
+    (0x2d, "xX"),
+    (0x2e, "cC"),
+    (0x2f, "vV"),
+    (0x30, "bB"),
+    (0x31, "nN"),
+    (0x32, "mM"),
+    (0x33, ",<"),
+    (0x34, ".>"),
+    (0x35, "/?"),
+    (0x39, "  "),
+];
+
+const UK_KEYS: [(u32, &str); 49] = [
+    (0x29, "`¬¦"),
+    (0x02, "1!"),
+    (0x03, "2\""),
+    (0x04, "3£"),
+    (0x05, "4$€"),
+    (0x06, "5%"),
+    (0x07, "6^"),
+    (0x08, "7&"),
+    (0x09, "8*"),
+    (0x0a, "9("),
+    (0x0b, "0)"),
+    (0x0c, "-_"),
+    (0x0d, "=+"),
+    (0x10, "qQ"),
+    (0x11, "wW"),
+    (0x12, "eEéÉ"),
+    (0x13, "rR"),
+    (0x14, "tT"),
+    (0x15, "yY"),
+    (0x16, "uUúÚ"),
+    (0x17, "iIíÍ"),
+    (0x18, "oOóÓ"),
+    (0x19, "pP"),
+    (0x1a, "[{"),
+    (0x1b, "]}"),
+    (0x2b, "#~"),
+    (0x1e, "aAáÁ"),
+    (0x1f, "sS"),
+    (0x20, "dD"),
+    (0x21, "fF"),
+    (0x22, "gG"),
+    (0x23, "hH"),
+    (0x24, "jJ"),
+    (0x25, "kK"),
+    (0x26, "lL"),
+    (0x27, ";:"),
+    (0x28, "'@"),
+    (0x56, "\\|"),
+    (0x2c, "zZ"),
+    (0x2d, "xX"),
+    (0x2e, "cC"),
+    (0x2f, "vV"),
+    (0x30, "bB"),
+    (0x31, "nN"),
+    (0x32, "mM"),
+    (0x33, ",<"),
+    (0x34, ".>"),
+    (0x35, "/?"),
+    (0x39, "  "),
+];
+
+const DE_KEYS: [(u32, &str); 49] = [
+    (0x29, "\u{302}°"),
+    (0x02, "1!"),
+    (0x03, "2\"²"),
+    (0x04, "3§³"),
+    (0x05, "4$"),
+    (0x06, "5%"),
+    (0x07, "6&"),
+    (0x08, "7/{"),
+    (0x09, "8(["),
+    (0x0a, "9)]"),
+    (0x0b, "0=}"),
+    (0x0c, "ß?\\"),
+    (0x0d, "\u{301}\u{300}"),
+    (0x10, "qQ@"),
+    (0x11, "wW"),
+    (0x12, "eE€"),
+    (0x13, "rR"),
+    (0x14, "tT"),
+    (0x15, "zZ"),
+    (0x16, "uU"),
+    (0x17, "iI"),
+    (0x18, "oO"),
+    (0x19, "pP"),
+    (0x1a, "üÜ"),
+    (0x1b, "+*~"),
+    (0x2b, "#'"),
+    (0x1e, "aA"),
+    (0x1f, "sS"),
+    (0x20, "dD"),
+    (0x21, "fF"),
+    (0x22, "gG"),
+    (0x23, "hH"),
+    (0x24, "jJ"),
+    (0x25, "kK"),
+    (0x26, "lL"),
+    (0x27, "öÖ"),
+    (0x28, "äÄ"),
+    (0x56, "<>|"),
+    (0x2c, "yY"),
+    (0x2d, "xX"),
+    (0x2e, "cC"),
+    (0x2f, "vV"),
+    (0x30, "bB"),
+    (0x31, "nN"),
+    (0x32, "mMµ"),
+    (0x33, ",;"),
+    (0x34, ".:"),
+    (0x35, "-_"),
+    (0x39, "  "),
+];
+
+const FR_KEYS: [(u32, &str); 49] = [
+    (0x29, "²"),
+    (0x02, "&1"),
+    (0x03, "é2\u{303}"),
+    (0x04, "\"3#"),
+    (0x05, "'4{"),
+    (0x06, "(5["),
+    (0x07, "-6|"),
+    (0x08, "è7\u{300}"),
+    (0x09, "_8\\"),
+    (0x0a, "ç9^"),
+    (0x0b, "à0@"),
+    (0x0c, ")°]"),
+    (0x0d, "=+}"),
+    (0x10, "aA"),
+    (0x11, "zZ"),
+    (0x12, "eE€"),
+    (0x13, "rR"),
+    (0x14, "tT"),
+    (0x15, "yY"),
+    (0x16, "uU"),
+    (0x17, "iI"),
+    (0x18, "oO"),
+    (0x19, "pP"),
+    (0x1a, "\u{302}\u{308}"),
+    (0x1b, "$£¤"),
+    (0x2b, "*µ"),
+    (0x1e, "qQ"),
+    (0x1f, "sS"),
+    (0x20, "dD"),
+    (0x21, "fF"),
+    (0x22, "gG"),
+    (0x23, "hH"),
+    (0x24, "jJ"),
+    (0x25, "kK"),
+    (0x26, "lL"),
+    (0x27, "mM"),
+    (0x28, "ù%"),
+    (0x56, "<>"),
+    (0x2c, "wW"),
+    (0x2d, "xX"),
+    (0x2e, "cC"),
+    (0x2f, "vV"),
+    (0x30, "bB"),
+    (0x31, "nN"),
+    (0x32, ",?"),
+    (0x33, ";."),
+    (0x34, ":/"),
+    (0x35, "!§"),
+    (0x39, "  "),
+];
+
+const JIS_KEYS: [(u32, &str); 49] = [
+    (0x02, "1!"),
+    (0x03, "2\""),
+    (0x04, "3#"),
+    (0x05, "4$"),
+    (0x06, "5%"),
+    (0x07, "6&"),
+    (0x08, "7'"),
+    (0x09, "8("),
+    (0x0a, "9)"),
+    (0x0b, "0"),
+    (0x0c, "-="),
+    (0x0d, "^~"),
+    (0x7d, "¥|"),
+    (0x10, "qQ"),
+    (0x11, "wW"),
+    (0x12, "eE"),
+    (0x13, "rR"),
+    (0x14, "tT"),
+    (0x15, "yY"),
+    (0x16, "uU"),
+    (0x17, "iI"),
+    (0x18, "oO"),
+    (0x19, "pP"),
+    (0x1a, "@`"),
+    (0x1b, "[{"),
+    (0x1e, "aA"),
+    (0x1f, "sS"),
+    (0x20, "dD"),
+    (0x21, "fF"),
+    (0x22, "gG"),
+    (0x23, "hH"),
+    (0x24, "jJ"),
+    (0x25, "kK"),
+    (0x26, "lL"),
+    (0x27, ";+"),
+    (0x28, ":*"),
+    (0x2b, "]}"),
+    (0x2c, "zZ"),
+    (0x2d, "xX"),
+    (0x2e, "cC"),
+    (0x2f, "vV"),
+    (0x30, "bB"),
+    (0x31, "nN"),
+    (0x32, "mM"),
+    (0x33, ",<"),
+    (0x34, ".>"),
+    (0x35, "/?"),
+    (0x73, "\\_"),
+    (0x39, "  "),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(layout: &KeyboardLayout, keys: &[(u32, Modifiers)]) -> String {
+        let mut composer = DeadKeyComposer::new();
+        keys.iter()
+            .filter_map(|(sc, m)| layout.text(ScanCode(*sc), *m, &mut composer))
+            .collect()
+    }
+
+    #[test]
+    fn layout_symbol() {
+        let none = Modifiers::new();
+        let shift = Modifiers::from(Modifier::LShift);
+        let us = KeyboardLayout::US;
+        assert!(us.symbol(ScanCode(0x1e), none) == Some(KeySymbol::Char('a')));
+        assert!(us.symbol(ScanCode(0x1e), shift) == Some(KeySymbol::Char('A')));
+        assert!(us.symbol(ScanCode(0x03), shift) == Some(KeySymbol::Char('@')));
+        assert!(us.symbol(ScanCode(0x1e), Modifier::LCtrl.into()).is_none());
+        assert!(us.symbol(ScanCode(0x1e), Modifier::RAlt.into()).is_none());
+        assert!(us.symbol(ScanCode(0x3b), none).is_none());
+        let caps = Modifiers::from(Modifier::CapsLock);
+        assert!(us.symbol(ScanCode(0x1e), caps) == Some(KeySymbol::Char('A')));
+        assert!(us.symbol(ScanCode(0x1e), caps | Modifier::Shift) == Some(KeySymbol::Char('a')));
+        assert!(us.symbol(ScanCode(0x02), caps) == Some(KeySymbol::Char('1')));
+        assert!(KeyboardLayout::UK.symbol(ScanCode(0x03), shift) == Some(KeySymbol::Char('"')));
+        let de = KeyboardLayout::DE;
+        assert!(de.symbol(ScanCode(0x15), none) == Some(KeySymbol::Char('z')));
+        assert!(de.symbol(ScanCode(0x28), caps) == Some(KeySymbol::Char('Ä')));
+        assert!(de.symbol(ScanCode(0x10), Modifier::RAlt.into()) == Some(KeySymbol::Char('@')));
+        let ctrl_alt = Modifier::LCtrl | Modifier::LAlt;
+        assert!(de.symbol(ScanCode(0x12), ctrl_alt) == Some(KeySymbol::Char('€')));
+        assert!(de.symbol(ScanCode(0x29), none) == Some(KeySymbol::Dead('\u{302}')));
+        let fr = KeyboardLayout::FR;
+        assert!(fr.symbol(ScanCode(0x10), none) == Some(KeySymbol::Char('a')));
+        assert!(fr.symbol(ScanCode(0x02), shift) == Some(KeySymbol::Char('1')));
+        let jis = KeyboardLayout::JIS;
+        assert!(jis.symbol(ScanCode(0x7d), none) == Some(KeySymbol::Char('¥')));
+        assert!(jis.symbol(ScanCode(0x0b), shift).is_none());
+    }
+
+    #[test]
+    fn layout_dead_keys() {
+        let none = Modifiers::new();
+        let shift = Modifiers::from(Modifier::Shift);
+        let de = KeyboardLayout::DE;
+        assert!(text(&de, &[(0x29, none), (0x12, none)]) == "ê");
+        assert!(text(&de, &[(0x0d, shift), (0x1e, shift)]) == "À");
+        assert!(text(&de, &[(0x29, none), (0x39, none)]) == "^");
+        assert!(text(&de, &[(0x29, none), (0x29, none)]) == "^");
+        assert!(text(&de, &[(0x29, none), (0x2d, none)]) == "^x");
+        assert!(text(&de, &[(0x29, none), (0x0d, none), (0x12, none)]) == "^é");
+        let fr = KeyboardLayout::FR;
+        assert!(text(&fr, &[(0x1a, shift), (0x17, none)]) == "ï");
+        let mut composer = DeadKeyComposer::new();
+        assert!(fr.text(ScanCode(0x1a), none, &mut composer).is_none());
+        assert!(composer.pending() == Some('\u{302}'));
+        composer.reset();
+        assert!(fr.text(ScanCode(0x12), none, &mut composer) == Some("e".to_string()));
+    }
+
+    #[test]
+    fn layout_keystrokes() {
+        let de = KeyboardLayout::DE;
+        let shift = Modifiers::from(Modifier::LShift);
+        assert!(de.keystrokes('Y') == Some(vec![(ScanCode(0x2c), shift)]));
+        assert!(de.keystrokes('€') == Some(vec![(ScanCode(0x12), Modifier::RAlt.into())]));
+        assert!(
+            de.keystrokes('Ê')
+                == Some(vec![
+                    (ScanCode(0x29), Modifiers::new()),
+                    (ScanCode(0x12), shift)
+                ])
+        );
+        assert!(
+            de.keystrokes('^')
+                == Some(vec![
+                    (ScanCode(0x29), Modifiers::new()),
+                    (ScanCode(0x39), Modifiers::new())
+                ])
+        );
+        assert!(de.keystrokes('ñ').is_none());
+        assert!(KeyboardLayout::US.keystrokes('é').is_none());
+        for c in "Hallo Welt, äöü ß! ÀÉ ^`´".chars() {
+            let mut composer = DeadKeyComposer::new();
+            let text: String = de
+                .keystrokes(c)
+                .unwrap()
+                .into_iter()
+                .filter_map(|(sc, m)| de.text(sc, m, &mut composer))
+                .collect();
+            assert!(text == c.to_string());
+        }
+    }
+}
@@ -0,0 +1,459 @@
+use super::evdev::*;
+use super::*;
+
+/// A Carbon virtual key code (`kVK_*` in HIToolbox/Events.h).
+///
+/// These name physical key positions on an ANSI keyboard, not the characters they produce.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacKeyCode(pub u16);
+
+impl MacKeyCode {
+    pub const ANSI_A: Self = Self(0x00);
+    pub const ANSI_S: Self = Self(0x01);
+    pub const ANSI_D: Self = Self(0x02);
+    pub const ANSI_F: Self = Self(0x03);
+    pub const ANSI_H: Self = Self(0x04);
+    pub const ANSI_G: Self = Self(0x05);
+    pub const ANSI_Z: Self = Self(0x06);
+    pub const ANSI_X: Self = Self(0x07);
+    pub const ANSI_C: Self = Self(0x08);
+    pub const ANSI_V: Self = Self(0x09);
+    pub const ISO_SECTION: Self = Self(0x0A);
+    pub const ANSI_B: Self = Self(0x0B);
+    pub const ANSI_Q: Self = Self(0x0C);
+    pub const ANSI_W: Self = Self(0x0D);
+    pub const ANSI_E: Self = Self(0x0E);
+    pub const ANSI_R: Self = Self(0x0F);
+    pub const ANSI_Y: Self = Self(0x10);
+    pub const ANSI_T: Self = Self(0x11);
+    pub const ANSI_1: Self = Self(0x12);
+    pub const ANSI_2: Self = Self(0x13);
+    pub const ANSI_3: Self = Self(0x14);
+    pub const ANSI_4: Self = Self(0x15);
+    pub const ANSI_6: Self = Self(0x16);
+    pub const ANSI_5: Self = Self(0x17);
+    pub const ANSI_EQUAL: Self = Self(0x18);
+    pub const ANSI_9: Self = Self(0x19);
+    pub const ANSI_7: Self = Self(0x1A);
+    pub const ANSI_MINUS: Self = Self(0x1B);
+    pub const ANSI_8: Self = Self(0x1C);
+    pub const ANSI_0: Self = Self(0x1D);
+    pub const ANSI_RIGHT_BRACKET: Self = Self(0x1E);
+    pub const ANSI_O: Self = Self(0x1F);
+    pub const ANSI_U: Self = Self(0x20);
+    pub const ANSI_LEFT_BRACKET: Self = Self(0x21);
+    pub const ANSI_I: Self = Self(0x22);
+    pub const ANSI_P: Self = Self(0x23);
+    pub const RETURN: Self = Self(0x24);
+    pub const ANSI_L: Self = Self(0x25);
+    pub const ANSI_J: Self = Self(0x26);
+    pub const ANSI_QUOTE: Self = Self(0x27);
+    pub const ANSI_K: Self = Self(0x28);
+    pub const ANSI_SEMICOLON: Self = Self(0x29);
+    pub const ANSI_BACKSLASH: Self = Self(0x2A);
+    pub const ANSI_COMMA: Self = Self(0x2B);
+    pub const ANSI_SLASH: Self = Self(0x2C);
+    pub const ANSI_N: Self = Self(0x2D);
+    pub const ANSI_M: Self = Self(0x2E);
+    pub const ANSI_PERIOD: Self = Self(0x2F);
+    pub const TAB: Self = Self(0x30);
+    pub const SPACE: Self = Self(0x31);
+    pub const ANSI_GRAVE: Self = Self(0x32);
+    pub const DELETE: Self = Self(0x33);
+    pub const ESCAPE: Self = Self(0x35);
+    pub const RIGHT_COMMAND: Self = Self(0x36);
+    pub const COMMAND: Self = Self(0x37);
+    pub const SHIFT: Self = Self(0x38);
+    pub const CAPS_LOCK: Self = Self(0x39);
+    pub const OPTION: Self = Self(0x3A);
+    pub const CONTROL: Self = Self(0x3B);
+    pub const RIGHT_SHIFT: Self = Self(0x3C);
+    pub const RIGHT_OPTION: Self = Self(0x3D);
+    pub const RIGHT_CONTROL: Self = Self(0x3E);
+    pub const FUNCTION: Self = Self(0x3F);
+    pub const F17: Self = Self(0x40);
+    pub const ANSI_KEYPAD_DECIMAL: Self = Self(0x41);
+    pub const ANSI_KEYPAD_MULTIPLY: Self = Self(0x43);
+    pub const ANSI_KEYPAD_PLUS: Self = Self(0x45);
+    pub const ANSI_KEYPAD_CLEAR: Self = Self(0x47);
+    pub const VOLUME_UP: Self = Self(0x48);
+    pub const VOLUME_DOWN: Self = Self(0x49);
+    pub const MUTE: Self = Self(0x4A);
+    pub const ANSI_KEYPAD_DIVIDE: Self = Self(0x4B);
+    pub const ANSI_KEYPAD_ENTER: Self = Self(0x4C);
+    pub const ANSI_KEYPAD_MINUS: Self = Self(0x4E);
+    pub const F18: Self = Self(0x4F);
+    pub const F19: Self = Self(0x50);
+    pub const ANSI_KEYPAD_EQUALS: Self = Self(0x51);
+    pub const ANSI_KEYPAD_0: Self = Self(0x52);
+    pub const ANSI_KEYPAD_1: Self = Self(0x53);
+    pub const ANSI_KEYPAD_2: Self = Self(0x54);
+    pub const ANSI_KEYPAD_3: Self = Self(0x55);
+    pub const ANSI_KEYPAD_4: Self = Self(0x56);
+    pub const ANSI_KEYPAD_5: Self = Self(0x57);
+    pub const ANSI_KEYPAD_6: Self = Self(0x58);
+    pub const ANSI_KEYPAD_7: Self = Self(0x59);
+    pub const F20: Self = Self(0x5A);
+    pub const ANSI_KEYPAD_8: Self = Self(0x5B);
+    pub const ANSI_KEYPAD_9: Self = Self(0x5C);
+    pub const JIS_YEN: Self = Self(0x5D);
+    pub const JIS_UNDERSCORE: Self = Self(0x5E);
+    pub const JIS_KEYPAD_COMMA: Self = Self(0x5F);
+    pub const F5: Self = Self(0x60);
+    pub const F6: Self = Self(0x61);
+    pub const F7: Self = Self(0x62);
+    pub const F3: Self = Self(0x63);
+    pub const F8: Self = Self(0x64);
+    pub const F9: Self = Self(0x65);
+    pub const JIS_EISU: Self = Self(0x66);
+    pub const F11: Self = Self(0x67);
+    pub const JIS_KANA: Self = Self(0x68);
+    pub const F13: Self = Self(0x69);
+    pub const F16: Self = Self(0x6A);
+    pub const F14: Self = Self(0x6B);
+    pub const F10: Self = Self(0x6D);
+    pub const CONTEXTUAL_MENU: Self = Self(0x6E);
+    pub const F12: Self = Self(0x6F);
+    pub const F15: Self = Self(0x71);
+    pub const HELP: Self = Self(0x72);
+    pub const HOME: Self = Self(0x73);
+    pub const PAGE_UP: Self = Self(0x74);
+    pub const FORWARD_DELETE: Self = Self(0x75);
+    pub const F4: Self = Self(0x76);
+    pub const END: Self = Self(0x77);
+    pub const F2: Self = Self(0x78);
+    pub const PAGE_DOWN: Self = Self(0x79);
+    pub const F1: Self = Self(0x7A);
+    pub const LEFT_ARROW: Self = Self(0x7B);
+    pub const RIGHT_ARROW: Self = Self(0x7C);
+    pub const DOWN_ARROW: Self = Self(0x7D);
+    pub const UP_ARROW: Self = Self(0x7E);
+}
+
+impl std::fmt::Display for MacKeyCode {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:02X}", self.0)
+    }
+}
+
+fn evdev_from_mac_key_code(key_code: MacKeyCode) -> Option<u32> {
+    let code = match key_code {
+        MacKeyCode::ANSI_A => KEY_A,
+        MacKeyCode::ANSI_S => KEY_S,
+        MacKeyCode::ANSI_D => KEY_D,
+        MacKeyCode::ANSI_F => KEY_F,
+        MacKeyCode::ANSI_H => KEY_H,
+        MacKeyCode::ANSI_G => KEY_G,
+        MacKeyCode::ANSI_Z => KEY_Z,
+        MacKeyCode::ANSI_X => KEY_X,
+        MacKeyCode::ANSI_C => KEY_C,
+        MacKeyCode::ANSI_V => KEY_V,
+        MacKeyCode::ISO_SECTION => KEY_102ND,
+        MacKeyCode::ANSI_B => KEY_B,
+        MacKeyCode::ANSI_Q => KEY_Q,
+        MacKeyCode::ANSI_W => KEY_W,
+        MacKeyCode::ANSI_E => KEY_E,
+        MacKeyCode::ANSI_R => KEY_R,
+        MacKeyCode::ANSI_Y => KEY_Y,
+        MacKeyCode::ANSI_T => KEY_T,
+        MacKeyCode::ANSI_1 => KEY_1,
+        MacKeyCode::ANSI_2 => KEY_2,
+        MacKeyCode::ANSI_3 => KEY_3,
+        MacKeyCode::ANSI_4 => KEY_4,
+        MacKeyCode::ANSI_6 => KEY_6,
+        MacKeyCode::ANSI_5 => KEY_5,
+        MacKeyCode::ANSI_EQUAL => KEY_EQUAL,
+        MacKeyCode::ANSI_9 => KEY_9,
+        MacKeyCode::ANSI_7 => KEY_7,
+        MacKeyCode::ANSI_MINUS => KEY_MINUS,
+        MacKeyCode::ANSI_8 => KEY_8,
+        MacKeyCode::ANSI_0 => KEY_0,
+        MacKeyCode::ANSI_RIGHT_BRACKET => KEY_RIGHTBRACE,
+        MacKeyCode::ANSI_O => KEY_O,
+        MacKeyCode::ANSI_U => KEY_U,
+        MacKeyCode::ANSI_LEFT_BRACKET => KEY_LEFTBRACE,
+        MacKeyCode::ANSI_I => KEY_I,
+        MacKeyCode::ANSI_P => KEY_P,
+        MacKeyCode::RETURN => KEY_ENTER,
+        MacKeyCode::ANSI_L => KEY_L,
+        MacKeyCode::ANSI_J => KEY_J,
+        MacKeyCode::ANSI_QUOTE => KEY_APOSTROPHE,
+        MacKeyCode::ANSI_K => KEY_K,
+        MacKeyCode::ANSI_SEMICOLON => KEY_SEMICOLON,
+        MacKeyCode::ANSI_BACKSLASH => KEY_BACKSLASH,
+        MacKeyCode::ANSI_COMMA => KEY_COMMA,
+        MacKeyCode::ANSI_SLASH => KEY_SLASH,
+        MacKeyCode::ANSI_N => KEY_N,
+        MacKeyCode::ANSI_M => KEY_M,
+        MacKeyCode::ANSI_PERIOD => KEY_DOT,
+        MacKeyCode::TAB => KEY_TAB,
+        MacKeyCode::SPACE => KEY_SPACE,
+        MacKeyCode::ANSI_GRAVE => KEY_GRAVE,
+        MacKeyCode::DELETE => KEY_BACKSPACE,
+        MacKeyCode::ESCAPE => KEY_ESC,
+        MacKeyCode::RIGHT_COMMAND => KEY_RIGHTMETA,
+        MacKeyCode::COMMAND => KEY_LEFTMETA,
+        MacKeyCode::SHIFT => KEY_LEFTSHIFT,
+        MacKeyCode::CAPS_LOCK => KEY_CAPSLOCK,
+        MacKeyCode::OPTION => KEY_LEFTALT,
+        MacKeyCode::CONTROL => KEY_LEFTCTRL,
+        MacKeyCode::RIGHT_SHIFT => KEY_RIGHTSHIFT,
+        MacKeyCode::RIGHT_OPTION => KEY_RIGHTALT,
+        MacKeyCode::RIGHT_CONTROL => KEY_RIGHTCTRL,
+        MacKeyCode::F17 => KEY_F17,
+        MacKeyCode::ANSI_KEYPAD_DECIMAL => KEY_KPDOT,
+        MacKeyCode::ANSI_KEYPAD_MULTIPLY => KEY_KPASTERISK,
+        MacKeyCode::ANSI_KEYPAD_PLUS => KEY_KPPLUS,
+        MacKeyCode::ANSI_KEYPAD_CLEAR => KEY_NUMLOCK,
+        MacKeyCode::VOLUME_UP => KEY_VOLUMEUP,
+        MacKeyCode::VOLUME_DOWN => KEY_VOLUMEDOWN,
+        MacKeyCode::MUTE => KEY_MUTE,
+        MacKeyCode::ANSI_KEYPAD_DIVIDE => KEY_KPSLASH,
+        MacKeyCode::ANSI_KEYPAD_ENTER => KEY_KPENTER,
+        MacKeyCode::ANSI_KEYPAD_MINUS => KEY_KPMINUS,
+        MacKeyCode::F18 => KEY_F18,
+        MacKeyCode::F19 => KEY_F19,
+        MacKeyCode::ANSI_KEYPAD_EQUALS => KEY_KPEQUAL,
+        MacKeyCode::ANSI_KEYPAD_0 => KEY_KP0,
+        MacKeyCode::ANSI_KEYPAD_1 => KEY_KP1,
+        MacKeyCode::ANSI_KEYPAD_2 => KEY_KP2,
+        MacKeyCode::ANSI_KEYPAD_3 => KEY_KP3,
+        MacKeyCode::ANSI_KEYPAD_4 => KEY_KP4,
+        MacKeyCode::ANSI_KEYPAD_5 => KEY_KP5,
+        MacKeyCode::ANSI_KEYPAD_6 => KEY_KP6,
+        MacKeyCode::ANSI_KEYPAD_7 => KEY_KP7,
+        MacKeyCode::F20 => KEY_F20,
+        MacKeyCode::ANSI_KEYPAD_8 => KEY_KP8,
+        MacKeyCode::ANSI_KEYPAD_9 => KEY_KP9,
+        MacKeyCode::JIS_YEN => KEY_YEN,
+        MacKeyCode::JIS_UNDERSCORE => KEY_RO,
+        MacKeyCode::JIS_KEYPAD_COMMA => KEY_KPCOMMA,
+        MacKeyCode::F5 => KEY_F5,
+        MacKeyCode::F6 => KEY_F6,
+        MacKeyCode::F7 => KEY_F7,
+        MacKeyCode::F3 => KEY_F3,
+        MacKeyCode::F8 => KEY_F8,
+        MacKeyCode::F9 => KEY_F9,
+        MacKeyCode::JIS_EISU => KEY_HANJA,
+        MacKeyCode::F11 => KEY_F11,
+        MacKeyCode::JIS_KANA => KEY_HANGEUL,
+        MacKeyCode::F13 => KEY_F13,
+        MacKeyCode::F16 => KEY_F16,
+        MacKeyCode::F14 => KEY_F14,
+        MacKeyCode::F10 => KEY_F10,
+        MacKeyCode::CONTEXTUAL_MENU => KEY_COMPOSE,
+        MacKeyCode::F12 => KEY_F12,
+        MacKeyCode::F15 => KEY_F15,
+        MacKeyCode::HELP => KEY_INSERT,
+        MacKeyCode::HOME => KEY_HOME,
+        MacKeyCode::PAGE_UP => KEY_PAGEUP,
+        MacKeyCode::FORWARD_DELETE => KEY_DELETE,
+        MacKeyCode::F4 => KEY_F4,
+        MacKeyCode::END => KEY_END,
+        MacKeyCode::F2 => KEY_F2,
+        MacKeyCode::PAGE_DOWN => KEY_PAGEDOWN,
+        MacKeyCode::F1 => KEY_F1,
+        MacKeyCode::LEFT_ARROW => KEY_LEFT,
+        MacKeyCode::RIGHT_ARROW => KEY_RIGHT,
+        MacKeyCode::DOWN_ARROW => KEY_DOWN,
+        MacKeyCode::UP_ARROW => KEY_UP,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn mac_key_code_from_evdev(code: u32) -> Option<MacKeyCode> {
+    let key_code = match code {
+        KEY_A => MacKeyCode::ANSI_A,
+        KEY_S => MacKeyCode::ANSI_S,
+        KEY_D => MacKeyCode::ANSI_D,
+        KEY_F => MacKeyCode::ANSI_F,
+        KEY_H => MacKeyCode::ANSI_H,
+        KEY_G => MacKeyCode::ANSI_G,
+        KEY_Z => MacKeyCode::ANSI_Z,
+        KEY_X => MacKeyCode::ANSI_X,
+        KEY_C => MacKeyCode::ANSI_C,
+        KEY_V => MacKeyCode::ANSI_V,
+        KEY_102ND => MacKeyCode::ISO_SECTION,
+        KEY_B => MacKeyCode::ANSI_B,
+        KEY_Q => MacKeyCode::ANSI_Q,
+        KEY_W => MacKeyCode::ANSI_W,
+        KEY_E => MacKeyCode::ANSI_E,
+        KEY_R => MacKeyCode::ANSI_R,
+        KEY_Y => MacKeyCode::ANSI_Y,
+        KEY_T => MacKeyCode::ANSI_T,
+        KEY_1 => MacKeyCode::ANSI_1,
+        KEY_2 => MacKeyCode::ANSI_2,
+        KEY_3 => MacKeyCode::ANSI_3,
+        KEY_4 => MacKeyCode::ANSI_4,
+        KEY_6 => MacKeyCode::ANSI_6,
+        KEY_5 => MacKeyCode::ANSI_5,
+        KEY_EQUAL => MacKeyCode::ANSI_EQUAL,
+        KEY_9 => MacKeyCode::ANSI_9,
+        KEY_7 => MacKeyCode::ANSI_7,
+        KEY_MINUS => MacKeyCode::ANSI_MINUS,
+        KEY_8 => MacKeyCode::ANSI_8,
+        KEY_0 => MacKeyCode::ANSI_0,
+        KEY_RIGHTBRACE => MacKeyCode::ANSI_RIGHT_BRACKET,
+        KEY_O => MacKeyCode::ANSI_O,
+        KEY_U => MacKeyCode::ANSI_U,
+        KEY_LEFTBRACE => MacKeyCode::ANSI_LEFT_BRACKET,
+        KEY_I => MacKeyCode::ANSI_I,
+        KEY_P => MacKeyCode::ANSI_P,
+        KEY_ENTER => MacKeyCode::RETURN,
+        KEY_L => MacKeyCode::ANSI_L,
+        KEY_J => MacKeyCode::ANSI_J,
+        KEY_APOSTROPHE => MacKeyCode::ANSI_QUOTE,
+        KEY_K => MacKeyCode::ANSI_K,
+        KEY_SEMICOLON => MacKeyCode::ANSI_SEMICOLON,
+        KEY_BACKSLASH => MacKeyCode::ANSI_BACKSLASH,
+        KEY_COMMA => MacKeyCode::ANSI_COMMA,
+        KEY_SLASH => MacKeyCode::ANSI_SLASH,
+        KEY_N => MacKeyCode::ANSI_N,
+        KEY_M => MacKeyCode::ANSI_M,
+        KEY_DOT => MacKeyCode::ANSI_PERIOD,
+        KEY_TAB => MacKeyCode::TAB,
+        KEY_SPACE => MacKeyCode::SPACE,
+        KEY_GRAVE => MacKeyCode::ANSI_GRAVE,
+        KEY_BACKSPACE => MacKeyCode::DELETE,
+        KEY_ESC => MacKeyCode::ESCAPE,
+        KEY_RIGHTMETA => MacKeyCode::RIGHT_COMMAND,
+        KEY_LEFTMETA => MacKeyCode::COMMAND,
+        KEY_LEFTSHIFT => MacKeyCode::SHIFT,
+        KEY_CAPSLOCK => MacKeyCode::CAPS_LOCK,
+        KEY_LEFTALT => MacKeyCode::OPTION,
+        KEY_LEFTCTRL => MacKeyCode::CONTROL,
+        KEY_RIGHTSHIFT => MacKeyCode::RIGHT_SHIFT,
+        KEY_RIGHTALT => MacKeyCode::RIGHT_OPTION,
+        KEY_RIGHTCTRL => MacKeyCode::RIGHT_CONTROL,
+        KEY_F17 => MacKeyCode::F17,
+        KEY_KPDOT => MacKeyCode::ANSI_KEYPAD_DECIMAL,
+        KEY_KPASTERISK => MacKeyCode::ANSI_KEYPAD_MULTIPLY,
+        KEY_KPPLUS => MacKeyCode::ANSI_KEYPAD_PLUS,
+        KEY_NUMLOCK => MacKeyCode::ANSI_KEYPAD_CLEAR,
+        KEY_VOLUMEUP => MacKeyCode::VOLUME_UP,
+        KEY_VOLUMEDOWN => MacKeyCode::VOLUME_DOWN,
+        KEY_MUTE => MacKeyCode::MUTE,
+        KEY_KPSLASH => MacKeyCode::ANSI_KEYPAD_DIVIDE,
+        KEY_KPENTER => MacKeyCode::ANSI_KEYPAD_ENTER,
+        KEY_KPMINUS => MacKeyCode::ANSI_KEYPAD_MINUS,
+        KEY_F18 => MacKeyCode::F18,
+        KEY_F19 => MacKeyCode::F19,
+        KEY_KPEQUAL => MacKeyCode::ANSI_KEYPAD_EQUALS,
+        KEY_KP0 => MacKeyCode::ANSI_KEYPAD_0,
+        KEY_KP1 => MacKeyCode::ANSI_KEYPAD_1,
+        KEY_KP2 => MacKeyCode::ANSI_KEYPAD_2,
+        KEY_KP3 => MacKeyCode::ANSI_KEYPAD_3,
+        KEY_KP4 => MacKeyCode::ANSI_KEYPAD_4,
+        KEY_KP5 => MacKeyCode::ANSI_KEYPAD_5,
+        KEY_KP6 => MacKeyCode::ANSI_KEYPAD_6,
+        KEY_KP7 => MacKeyCode::ANSI_KEYPAD_7,
+        KEY_F20 => MacKeyCode::F20,
+        KEY_KP8 => MacKeyCode::ANSI_KEYPAD_8,
+        KEY_KP9 => MacKeyCode::ANSI_KEYPAD_9,
+        KEY_YEN => MacKeyCode::JIS_YEN,
+        KEY_RO => MacKeyCode::JIS_UNDERSCORE,
+        KEY_KPCOMMA => MacKeyCode::JIS_KEYPAD_COMMA,
+        KEY_F5 => MacKeyCode::F5,
+        KEY_F6 => MacKeyCode::F6,
+        KEY_F7 => MacKeyCode::F7,
+        KEY_F3 => MacKeyCode::F3,
+        KEY_F8 => MacKeyCode::F8,
+        KEY_F9 => MacKeyCode::F9,
+        KEY_HANJA => MacKeyCode::JIS_EISU,
+        KEY_F11 => MacKeyCode::F11,
+        KEY_HANGEUL => MacKeyCode::JIS_KANA,
+        KEY_F13 => MacKeyCode::F13,
+        KEY_F16 => MacKeyCode::F16,
+        KEY_F14 => MacKeyCode::F14,
+        KEY_F10 => MacKeyCode::F10,
+        KEY_COMPOSE => MacKeyCode::CONTEXTUAL_MENU,
+        KEY_F12 => MacKeyCode::F12,
+        KEY_F15 => MacKeyCode::F15,
+        KEY_INSERT => MacKeyCode::HELP,
+        KEY_HOME => MacKeyCode::HOME,
+        KEY_PAGEUP => MacKeyCode::PAGE_UP,
+        KEY_DELETE => MacKeyCode::FORWARD_DELETE,
+        KEY_F4 => MacKeyCode::F4,
+        KEY_END => MacKeyCode::END,
+        KEY_F2 => MacKeyCode::F2,
+        KEY_PAGEDOWN => MacKeyCode::PAGE_DOWN,
+        KEY_F1 => MacKeyCode::F1,
+        KEY_LEFT => MacKeyCode::LEFT_ARROW,
+        KEY_RIGHT => MacKeyCode::RIGHT_ARROW,
+        KEY_DOWN => MacKeyCode::DOWN_ARROW,
+        KEY_UP => MacKeyCode::UP_ARROW,
+        _ => return None,
+    };
+    Some(key_code)
+}
+
+impl VirtualKey {
+    #[inline]
+    pub fn from_mac_key_code(key_code: MacKeyCode) -> Option<Self> {
+        evdev_from_mac_key_code(key_code).and_then(vkey_from_evdev)
+    }
+
+    #[inline]
+    pub fn to_mac_key_code(&self) -> Option<MacKeyCode> {
+        evdev_from_vkey(*self).and_then(mac_key_code_from_evdev)
+    }
+}
+
+impl ScanCode {
+    #[inline]
+    pub fn from_mac_key_code(key_code: MacKeyCode) -> Option<Self> {
+        evdev_from_mac_key_code(key_code).and_then(ScanCode::from_evdev)
+    }
+
+    #[inline]
+    pub fn to_mac_key_code(&self) -> Option<MacKeyCode> {
+        self.to_evdev().and_then(mac_key_code_from_evdev)
+    }
+}
+
+impl KeyCode {
+    #[inline]
+    pub fn from_mac_key_code(key_code: MacKeyCode) -> Option<Self> {
+        evdev_from_mac_key_code(key_code).map(KeyCode::from_evdev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mac_key_code_to_vkey() {
+        assert!(VirtualKey::from_mac_key_code(MacKeyCode::ANSI_A) == Some(VirtualKey::A));
+        assert!(VirtualKey::from_mac_key_code(MacKeyCode(0x00)) == Some(VirtualKey::A));
+        assert!(VirtualKey::from_mac_key_code(MacKeyCode::DELETE) == Some(VirtualKey::BackSpace));
+        assert!(
+            VirtualKey::from_mac_key_code(MacKeyCode::FORWARD_DELETE) == Some(VirtualKey::Delete)
+        );
+        assert!(VirtualKey::from_mac_key_code(MacKeyCode::FUNCTION).is_none());
+        assert!(VirtualKey::F1.to_mac_key_code() == Some(MacKeyCode::F1));
+        assert!(VirtualKey::Num7.to_mac_key_code() == Some(MacKeyCode(0x59)));
+        assert!(VirtualKey::PrintScreen.to_mac_key_code().is_none());
+        assert!(VirtualKey::Other(0x00).to_mac_key_code().is_none());
+    }
+
+    #[test]
+    fn mac_key_code_round_trip() {
+        for key_code in 0x00..0x80 {
+            let key_code = MacKeyCode(key_code);
+            if let Some(scan_code) = ScanCode::from_mac_key_code(key_code) {
+                assert!(
+                    scan_code.to_mac_key_code() == Some(key_code),
+                    "{}",
+                    key_code
+                );
+            }
+        }
+        let key = KeyCode::from_mac_key_code(MacKeyCode::RIGHT_CONTROL).unwrap();
+        assert!(key.vkey == VirtualKey::RCtrl);
+        assert!(key.scan_code == ScanCode(0xE01D));
+    }
+}
@@ -0,0 +1,532 @@
+use super::*;
+
+#[cfg(windows)]
+use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardState;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Modifier {
+    Shift,
+    Ctrl,
+    Alt,
+    Meta,
+    LShift,
+    RShift,
+    LCtrl,
+    RCtrl,
+    LAlt,
+    RAlt,
+    LMeta,
+    RMeta,
+    CapsLock,
+    NumLock,
+}
+
+const SHIFT: u32 = 0x01;
+const CTRL: u32 = 0x01 << 1;
+const ALT: u32 = 0x01 << 2;
+const META: u32 = 0x01 << 3;
+const LSHIFT: u32 = 0x01 << 4;
+const RSHIFT: u32 = 0x01 << 5;
+const LCTRL: u32 = 0x01 << 6;
+const RCTRL: u32 = 0x01 << 7;
+const LALT: u32 = 0x01 << 8;
+const RALT: u32 = 0x01 << 9;
+const LMETA: u32 = 0x01 << 10;
+const RMETA: u32 = 0x01 << 11;
+const CAPS_LOCK: u32 = 0x01 << 12;
+const NUM_LOCK: u32 = 0x01 << 13;
+#[cfg(feature = "serde")]
+const ALL: u32 = (0x01 << 14) - 1;
+
+impl Modifier {
+    const ORDER: [Modifier; 14] = [
+        Modifier::Ctrl,
+        Modifier::LCtrl,
+        Modifier::RCtrl,
+        Modifier::Alt,
+        Modifier::LAlt,
+        Modifier::RAlt,
+        Modifier::Shift,
+        Modifier::LShift,
+        Modifier::RShift,
+        Modifier::Meta,
+        Modifier::LMeta,
+        Modifier::RMeta,
+        Modifier::CapsLock,
+        Modifier::NumLock,
+    ];
+
+    fn as_u32(&self) -> u32 {
+        match self {
+            Self::Shift => SHIFT,
+            Self::Ctrl => CTRL,
+            Self::Alt => ALT,
+            Self::Meta => META,
+            Self::LShift => SHIFT | LSHIFT,
+            Self::RShift => SHIFT | RSHIFT,
+            Self::LCtrl => CTRL | LCTRL,
+            Self::RCtrl => CTRL | RCTRL,
+            Self::LAlt => ALT | LALT,
+            Self::RAlt => ALT | RALT,
+            Self::LMeta => META | LMETA,
+            Self::RMeta => META | RMETA,
+            Self::CapsLock => CAPS_LOCK,
+            Self::NumLock => NUM_LOCK,
+        }
+    }
+
+    fn sides(&self) -> u32 {
+        match self {
+            Self::Shift => LSHIFT | RSHIFT,
+            Self::Ctrl => LCTRL | RCTRL,
+            Self::Alt => LALT | RALT,
+            Self::Meta => LMETA | RMETA,
+            _ => 0,
+        }
+    }
+
+    pub(super) fn generic(&self) -> Self {
+        match self {
+            Self::LShift | Self::RShift => Self::Shift,
+            Self::LCtrl | Self::RCtrl => Self::Ctrl,
+            Self::LAlt | Self::RAlt => Self::Alt,
+            Self::LMeta | Self::RMeta => Self::Meta,
+            _ => *self,
+        }
+    }
+
+    #[inline]
+    pub fn from_vkey(vkey: VirtualKey) -> Option<Self> {
+        let m = match vkey {
+            VirtualKey::Shift => Self::Shift,
+            VirtualKey::Ctrl => Self::Ctrl,
+            VirtualKey::Alt => Self::Alt,
+            VirtualKey::LShift => Self::LShift,
+            VirtualKey::RShift => Self::RShift,
+            VirtualKey::LCtrl => Self::LCtrl,
+            VirtualKey::RCtrl => Self::RCtrl,
+            VirtualKey::LAlt => Self::LAlt,
+            VirtualKey::RAlt => Self::RAlt,
+            VirtualKey::LMeta => Self::LMeta,
+            VirtualKey::RMeta => Self::RMeta,
+            VirtualKey::CapsLock => Self::CapsLock,
+            VirtualKey::NumLock => Self::NumLock,
+            _ => return None,
+        };
+        Some(m)
+    }
+}
+
+impl std::fmt::Display for Modifier {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A set of modifier keys.
+///
+/// `Shift`, `Ctrl`, `Alt` and `Meta` are held when either side is held, so
+/// `contains(Modifier::Shift)` is true for a set built from `Modifier::LShift`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers(u32);
+
+impl Modifiers {
+    #[inline]
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    #[cfg(feature = "record")]
+    #[inline]
+    pub(crate) fn bits(&self) -> u32 {
+        self.0
+    }
+
+    #[cfg(feature = "record")]
+    #[inline]
+    pub(crate) fn from_bits(bits: u32) -> Self {
+        Self::normalize(bits)
+    }
+
+    /// Drops unknown bits and sets the generic bit of every side bit, as `insert` does.
+    #[cfg(feature = "serde")]
+    fn normalize(bits: u32) -> Self {
+        let mut bits = bits & ALL;
+        for m in [
+            Modifier::Shift,
+            Modifier::Ctrl,
+            Modifier::Alt,
+            Modifier::Meta,
+        ] {
+            if bits & m.sides() != 0 {
+                bits |= m.as_u32();
+            }
+        }
+        Self(bits)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub fn contains(&self, modifier: Modifier) -> bool {
+        let modifier = modifier.as_u32();
+        self.0 & modifier == modifier
+    }
+
+    #[inline]
+    pub fn insert(&mut self, modifier: Modifier) {
+        self.0 |= modifier.as_u32();
+    }
+
+    /// Removes `modifier`. Removing one side keeps the generic modifier while the other side
+    /// is still held; removing a generic modifier removes both sides.
+    pub fn remove(&mut self, modifier: Modifier) {
+        let generic = modifier.generic();
+        if generic == modifier {
+            self.0 &= !(modifier.as_u32() | modifier.sides());
+        } else {
+            self.0 &= !(modifier.as_u32() & !generic.as_u32());
+            if self.0 & generic.sides() == 0 {
+                self.0 &= !generic.as_u32();
+            }
+        }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> ModifiersIter {
+        ModifiersIter {
+            modifiers: *self,
+            index: 0,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<Modifier> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    pub fn without_sides(&self) -> Self {
+        Self(self.0 & (SHIFT | CTRL | ALT | META | CAPS_LOCK | NUM_LOCK))
+    }
+
+    #[inline]
+    pub fn without_locks(&self) -> Self {
+        Self(self.0 & !(CAPS_LOCK | NUM_LOCK))
+    }
+
+    /// Builds the set from a `GetKeyboardState`-style array, where the high bit of each
+    /// entry means the key is down and the low bit means it is toggled on.
+    pub fn from_keyboard_state(state: &[u8; 256]) -> Self {
+        const VK_SHIFT: usize = 0x10;
+        const VK_CONTROL: usize = 0x11;
+        const VK_MENU: usize = 0x12;
+        const VK_CAPITAL: usize = 0x14;
+        const VK_LWIN: usize = 0x5b;
+        const VK_RWIN: usize = 0x5c;
+        const VK_NUMLOCK: usize = 0x90;
+        const VK_LSHIFT: usize = 0xa0;
+        const VK_RSHIFT: usize = 0xa1;
+        const VK_LCONTROL: usize = 0xa2;
+        const VK_RCONTROL: usize = 0xa3;
+        const VK_LMENU: usize = 0xa4;
+        const VK_RMENU: usize = 0xa5;
+        let down = |vk: usize| state[vk] & 0x80 != 0;
+        let toggled = |vk: usize| state[vk] & 0x01 != 0;
+        let mut modifiers = Modifiers::new();
+        for (vk, m) in [
+            (VK_SHIFT, Modifier::Shift),
+            (VK_CONTROL, Modifier::Ctrl),
+            (VK_MENU, Modifier::Alt),
+            (VK_LSHIFT, Modifier::LShift),
+            (VK_RSHIFT, Modifier::RShift),
+            (VK_LCONTROL, Modifier::LCtrl),
+            (VK_RCONTROL, Modifier::RCtrl),
+            (VK_LMENU, Modifier::LAlt),
+            (VK_RMENU, Modifier::RAlt),
+            (VK_LWIN, Modifier::LMeta),
+            (VK_RWIN, Modifier::RMeta),
+        ] {
+            if down(vk) {
+                modifiers |= m;
+            }
+        }
+        if toggled(VK_CAPITAL) {
+            modifiers |= Modifier::CapsLock;
+        }
+        if toggled(VK_NUMLOCK) {
+            modifiers |= Modifier::NumLock;
+        }
+        modifiers
+    }
+
+    #[cfg(windows)]
+    pub fn from_key_state() -> Self {
+        let mut state = [0u8; 256];
+        if unsafe { GetKeyboardState(&mut state) }.is_err() {
+            return Self::new();
+        }
+        Self::from_keyboard_state(&state)
+    }
+}
+
+impl Default for Modifiers {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Modifiers {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl std::fmt::Display for Modifiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, m) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, "+")?;
+            }
+            write!(f, "{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ModifiersIter {
+    modifiers: Modifiers,
+    index: usize,
+}
+
+impl Iterator for ModifiersIter {
+    type Item = Modifier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(m) = Modifier::ORDER.get(self.index).copied() {
+            self.index += 1;
+            // A generic modifier is only reported when neither side is known.
+            if self.modifiers.contains(m) && self.modifiers.0 & m.sides() == 0 {
+                return Some(m);
+            }
+        }
+        None
+    }
+}
+
+impl IntoIterator for Modifiers {
+    type Item = Modifier;
+    type IntoIter = ModifiersIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Modifiers {
+    type Item = Modifier;
+    type IntoIter = ModifiersIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Modifier> for Modifiers {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Modifier>>(iter: I) -> Self {
+        iter.into_iter().fold(Modifiers::new(), |r, m| r | m)
+    }
+}
+
+impl std::ops::BitOr for Modifier {
+    type Output = Modifiers;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Modifiers(self.as_u32() | rhs.as_u32())
+    }
+}
+
+impl std::ops::BitOr<Modifiers> for Modifier {
+    type Output = Modifiers;
+
+    #[inline]
+    fn bitor(self, rhs: Modifiers) -> Self::Output {
+        Modifiers(self.as_u32() | rhs.0)
+    }
+}
+
+impl std::ops::BitOr<Modifier> for Modifiers {
+    type Output = Modifiers;
+
+    #[inline]
+    fn bitor(self, rhs: Modifier) -> Self::Output {
+        Modifiers(self.0 | rhs.as_u32())
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Modifiers {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl std::ops::BitOrAssign<Modifier> for Modifiers {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Modifier) {
+        self.0 |= rhs.as_u32();
+    }
+}
+
+impl From<Modifier> for Modifiers {
+    #[inline]
+    fn from(value: Modifier) -> Self {
+        Modifiers(value.as_u32())
+    }
+}
+
+impl<const N: usize> From<[Modifier; N]> for Modifiers {
+    #[inline]
+    fn from(value: [Modifier; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl From<&[Modifier]> for Modifiers {
+    #[inline]
+    fn from(value: &[Modifier]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Modifiers {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter())
+        } else {
+            serializer.serialize_u32(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Modifiers {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let v = Vec::<Modifier>::deserialize(deserializer)?;
+            Ok(v.into_iter().collect())
+        } else {
+            let bits = u32::deserialize(deserializer)?;
+            Ok(Modifiers::normalize(bits))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_contains() {
+        let m = Modifier::LShift | Modifier::Ctrl;
+        assert!(m.contains(Modifier::Shift));
+        assert!(m.contains(Modifier::LShift));
+        assert!(!m.contains(Modifier::RShift));
+        assert!(m.contains(Modifier::Ctrl));
+        assert!(!m.contains(Modifier::LCtrl));
+        assert!(!m.contains(Modifier::Alt));
+    }
+
+    #[test]
+    fn modifiers_iter() {
+        let m = Modifier::Shift | Modifier::LCtrl | Modifier::RCtrl | Modifier::CapsLock;
+        assert!(
+            m.to_vec()
+                == vec![
+                    Modifier::LCtrl,
+                    Modifier::RCtrl,
+                    Modifier::Shift,
+                    Modifier::CapsLock
+                ]
+        );
+        assert!(Modifiers::from(m.to_vec().as_slice()) == m);
+        assert!(m.to_string() == "LCtrl+RCtrl+Shift+CapsLock");
+        assert!(Modifiers::new().iter().next().is_none());
+    }
+
+    #[test]
+    fn modifiers_remove() {
+        let mut m = Modifier::LShift | Modifier::RShift | Modifier::Ctrl;
+        m.remove(Modifier::LShift);
+        assert!(m == Modifier::RShift | Modifier::Ctrl);
+        m.remove(Modifier::RShift);
+        assert!(m == Modifiers::from(Modifier::Ctrl));
+        m.insert(Modifier::LAlt);
+        m.remove(Modifier::Alt);
+        assert!(m == Modifiers::from(Modifier::Ctrl));
+    }
+
+    #[test]
+    fn modifiers_without_sides() {
+        let m = Modifier::LShift | Modifier::RAlt | Modifier::NumLock;
+        assert!(m.without_sides() == Modifier::Shift | Modifier::Alt | Modifier::NumLock);
+        assert!(m.without_locks() == Modifier::LShift | Modifier::RAlt);
+    }
+
+    #[test]
+    fn modifiers_from_keyboard_state() {
+        let mut state = [0u8; 256];
+        state[0x10] = 0x80;
+        state[0xa1] = 0x80;
+        state[0x5b] = 0x80;
+        state[0x14] = 0x01;
+        state[0x90] = 0x80;
+        let m = Modifiers::from_keyboard_state(&state);
+        assert!(m == Modifier::RShift | Modifier::LMeta | Modifier::CapsLock);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn modifiers_bare_side_bit() {
+        let m = Modifiers::normalize(LSHIFT | RALT | (0x01 << 20));
+        assert!(m == Modifier::LShift | Modifier::RAlt);
+        assert!(m.contains(Modifier::Shift));
+        assert!(m.contains(Modifier::LShift));
+        assert!(m.iter().count() == 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn modifiers_serde() {
+        let m = Modifier::Ctrl | Modifier::LShift;
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(json == r#"["Ctrl","LShift"]"#);
+        assert!(serde_json::from_str::<Modifiers>(&json).unwrap() == m);
+    }
+}
@@ -0,0 +1,430 @@
+use super::*;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseKeyError(pub String);
+
+impl std::fmt::Display for ParseKeyError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown key: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl VirtualKey {
+    /// Every key except `Other`, in declaration order.
+    pub(crate) const ALL: [Self; 138] = [
+        Self::Esc,
+        Self::Tab,
+        Self::CapsLock,
+        Self::Shift,
+        Self::Ctrl,
+        Self::Alt,
+        Self::LShift,
+        Self::RShift,
+        Self::LCtrl,
+        Self::RCtrl,
+        Self::LAlt,
+        Self::RAlt,
+        Self::LMeta,
+        Self::RMeta,
+        Self::ContextMenu,
+        Self::BackSpace,
+        Self::Enter,
+        Self::Space,
+        Self::PrintScreen,
+        Self::ScrollLock,
+        Self::Pause,
+        Self::Insert,
+        Self::Delete,
+        Self::Home,
+        Self::End,
+        Self::PageUp,
+        Self::PageDown,
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::Key1,
+        Self::Key2,
+        Self::Key3,
+        Self::Key4,
+        Self::Key5,
+        Self::Key6,
+        Self::Key7,
+        Self::Key8,
+        Self::Key9,
+        Self::Key0,
+        Self::A,
+        Self::B,
+        Self::C,
+        Self::D,
+        Self::E,
+        Self::F,
+        Self::G,
+        Self::H,
+        Self::I,
+        Self::J,
+        Self::K,
+        Self::L,
+        Self::M,
+        Self::N,
+        Self::O,
+        Self::P,
+        Self::Q,
+        Self::R,
+        Self::S,
+        Self::T,
+        Self::U,
+        Self::V,
+        Self::W,
+        Self::X,
+        Self::Y,
+        Self::Z,
+        Self::Semicolon,
+        Self::Equal,
+        Self::Comma,
+        Self::Minus,
+        Self::Period,
+        Self::Slash,
+        Self::Backquote,
+        Self::LBracket,
+        Self::Backslash,
+        Self::RBracket,
+        Self::Quote,
+        Self::IntlBackslash,
+        Self::F1,
+        Self::F2,
+        Self::F3,
+        Self::F4,
+        Self::F5,
+        Self::F6,
+        Self::F7,
+        Self::F8,
+        Self::F9,
+        Self::F10,
+        Self::F11,
+        Self::F12,
+        Self::F13,
+        Self::F14,
+        Self::F15,
+        Self::F16,
+        Self::F17,
+        Self::F18,
+        Self::F19,
+        Self::F20,
+        Self::F21,
+        Self::F22,
+        Self::F23,
+        Self::F24,
+        Self::NumLock,
+        Self::Num1,
+        Self::Num2,
+        Self::Num3,
+        Self::Num4,
+        Self::Num5,
+        Self::Num6,
+        Self::Num7,
+        Self::Num8,
+        Self::Num9,
+        Self::Num0,
+        Self::NumAdd,
+        Self::NumSub,
+        Self::NumMul,
+        Self::NumDiv,
+        Self::NumDecimal,
+        Self::NumEnter,
+        Self::VolumeMute,
+        Self::VolumeDown,
+        Self::VolumeUp,
+        Self::MediaNextTrack,
+        Self::MediaPrevTrack,
+        Self::MediaStop,
+        Self::MediaPlayPause,
+        Self::BrowserBack,
+        Self::BrowserForward,
+        Self::BrowserRefresh,
+        Self::BrowserStop,
+        Self::BrowserSearch,
+        Self::BrowserFavorites,
+        Self::BrowserHome,
+        Self::LaunchMail,
+        Self::LaunchMediaSelect,
+        Self::LaunchApp1,
+        Self::LaunchApp2,
+    ];
+
+    /// The canonical name used by `Display` and `FromStr`, e.g. `"PageUp"` or `"Num5"`.
+    ///
+    /// Names are fixed and do not follow the `Debug` output. Returns `None` for `Other`.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            Self::Esc => "Esc",
+            Self::Tab => "Tab",
+            Self::CapsLock => "CapsLock",
+            Self::Shift => "Shift",
+            Self::Ctrl => "Ctrl",
+            Self::Alt => "Alt",
+            Self::LShift => "LShift",
+            Self::RShift => "RShift",
+            Self::LCtrl => "LCtrl",
+            Self::RCtrl => "RCtrl",
+            Self::LAlt => "LAlt",
+            Self::RAlt => "RAlt",
+            Self::LMeta => "LMeta",
+            Self::RMeta => "RMeta",
+            Self::ContextMenu => "ContextMenu",
+            Self::BackSpace => "BackSpace",
+            Self::Enter => "Enter",
+            Self::Space => "Space",
+            Self::PrintScreen => "PrintScreen",
+            Self::ScrollLock => "ScrollLock",
+            Self::Pause => "Pause",
+            Self::Insert => "Insert",
+            Self::Delete => "Delete",
+            Self::Home => "Home",
+            Self::End => "End",
+            Self::PageUp => "PageUp",
+            Self::PageDown => "PageDown",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::Left => "Left",
+            Self::Right => "Right",
+            Self::Key1 => "Key1",
+            Self::Key2 => "Key2",
+            Self::Key3 => "Key3",
+            Self::Key4 => "Key4",
+            Self::Key5 => "Key5",
+            Self::Key6 => "Key6",
+            Self::Key7 => "Key7",
+            Self::Key8 => "Key8",
+            Self::Key9 => "Key9",
+            Self::Key0 => "Key0",
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+            Self::E => "E",
+            Self::F => "F",
+            Self::G => "G",
+            Self::H => "H",
+            Self::I => "I",
+            Self::J => "J",
+            Self::K => "K",
+            Self::L => "L",
+            Self::M => "M",
+            Self::N => "N",
+            Self::O => "O",
+            Self::P => "P",
+            Self::Q => "Q",
+            Self::R => "R",
+            Self::S => "S",
+            Self::T => "T",
+            Self::U => "U",
+            Self::V => "V",
+            Self::W => "W",
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Z => "Z",
+            Self::Semicolon => "Semicolon",
+            Self::Equal => "Equal",
+            Self::Comma => "Comma",
+            Self::Minus => "Minus",
+            Self::Period => "Period",
+            Self::Slash => "Slash",
+            Self::Backquote => "Backquote",
+            Self::LBracket => "LBracket",
+            Self::Backslash => "Backslash",
+            Self::RBracket => "RBracket",
+            Self::Quote => "Quote",
+            Self::IntlBackslash => "IntlBackslash",
+            Self::F1 => "F1",
+            Self::F2 => "F2",
+            Self::F3 => "F3",
+            Self::F4 => "F4",
+            Self::F5 => "F5",
+            Self::F6 => "F6",
+            Self::F7 => "F7",
+            Self::F8 => "F8",
+            Self::F9 => "F9",
+            Self::F10 => "F10",
+            Self::F11 => "F11",
+            Self::F12 => "F12",
+            Self::F13 => "F13",
+            Self::F14 => "F14",
+            Self::F15 => "F15",
+            Self::F16 => "F16",
+            Self::F17 => "F17",
+            Self::F18 => "F18",
+            Self::F19 => "F19",
+            Self::F20 => "F20",
+            Self::F21 => "F21",
+            Self::F22 => "F22",
+            Self::F23 => "F23",
+            Self::F24 => "F24",
+            Self::NumLock => "NumLock",
+            Self::Num1 => "Num1",
+            Self::Num2 => "Num2",
+            Self::Num3 => "Num3",
+            Self::Num4 => "Num4",
+            Self::Num5 => "Num5",
+            Self::Num6 => "Num6",
+            Self::Num7 => "Num7",
+            Self::Num8 => "Num8",
+            Self::Num9 => "Num9",
+            Self::Num0 => "Num0",
+            Self::NumAdd => "NumAdd",
+            Self::NumSub => "NumSub",
+            Self::NumMul => "NumMul",
+            Self::NumDiv => "NumDiv",
+            Self::NumDecimal => "NumDecimal",
+            Self::NumEnter => "NumEnter",
+            Self::VolumeMute => "VolumeMute",
+            Self::VolumeDown => "VolumeDown",
+            Self::VolumeUp => "VolumeUp",
+            Self::MediaNextTrack => "MediaNextTrack",
+            Self::MediaPrevTrack => "MediaPrevTrack",
+            Self::MediaStop => "MediaStop",
+            Self::MediaPlayPause => "MediaPlayPause",
+            Self::BrowserBack => "BrowserBack",
+            Self::BrowserForward => "BrowserForward",
+            Self::BrowserRefresh => "BrowserRefresh",
+            Self::BrowserStop => "BrowserStop",
+            Self::BrowserSearch => "BrowserSearch",
+            Self::BrowserFavorites => "BrowserFavorites",
+            Self::BrowserHome => "BrowserHome",
+            Self::LaunchMail => "LaunchMail",
+            Self::LaunchMediaSelect => "LaunchMediaSelect",
+            Self::LaunchApp1 => "LaunchApp1",
+            Self::LaunchApp2 => "LaunchApp2",
+            Self::Other(_) => return None,
+        };
+        Some(name)
+    }
+}
+
+impl std::fmt::Display for VirtualKey {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other(v) => write!(f, "Other({})", v),
+            _ => write!(f, "{}", self.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// Parses canonical names and aliases case-insensitively. Digits and punctuation are also
+/// accepted as their characters, e.g. `"1"` or `";"`, and `Other(n)` as `"Other(n)"`.
+impl std::str::FromStr for VirtualKey {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(vkey) = VirtualKey::ALL
+            .iter()
+            .find(|k| k.name().is_some_and(|name| name.eq_ignore_ascii_case(s)))
+        {
+            return Ok(*vkey);
+        }
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next())
+            && let Some((_, vkey)) = CHAR_KEYS.iter().find(|(k, _)| *k == c)
+        {
+            return Ok(*vkey);
+        }
+        if let Some((_, vkey)) = ALIASES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Ok(*vkey);
+        }
+        s.get(..6)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("other("))
+            .and_then(|_| s[6..].strip_suffix(')'))
+            .and_then(|v| v.parse().ok())
+            .map(VirtualKey::Other)
+            .ok_or_else(|| ParseKeyError(s.to_string()))
+    }
+}
+
+pub(super) const CHAR_KEYS: [(char, VirtualKey); 21] = [
+    ('0', VirtualKey::Key0),
+    ('1', VirtualKey::Key1),
+    ('2', VirtualKey::Key2),
+    ('3', VirtualKey::Key3),
+    ('4', VirtualKey::Key4),
+    ('5', VirtualKey::Key5),
+    ('6', VirtualKey::Key6),
+    ('7', VirtualKey::Key7),
+    ('8', VirtualKey::Key8),
+    ('9', VirtualKey::Key9),
+    (';', VirtualKey::Semicolon),
+    ('=', VirtualKey::Equal),
+    (',', VirtualKey::Comma),
+    ('-', VirtualKey::Minus),
+    ('.', VirtualKey::Period),
+    ('/', VirtualKey::Slash),
+    ('`', VirtualKey::Backquote),
+    ('[', VirtualKey::LBracket),
+    ('\\', VirtualKey::Backslash),
+    (']', VirtualKey::RBracket),
+    ('\'', VirtualKey::Quote),
+];
+
+const ALIASES: [(&str, VirtualKey); 18] = [
+    ("Escape", VirtualKey::Esc),
+    ("Return", VirtualKey::Enter),
+    ("Del", VirtualKey::Delete),
+    ("Ins", VirtualKey::Insert),
+    ("PgUp", VirtualKey::PageUp),
+    ("PgDn", VirtualKey::PageDown),
+    ("PrtSc", VirtualKey::PrintScreen),
+    ("Control", VirtualKey::Ctrl),
+    ("LControl", VirtualKey::LCtrl),
+    ("RControl", VirtualKey::RCtrl),
+    ("Menu", VirtualKey::ContextMenu),
+    ("Apps", VirtualKey::ContextMenu),
+    ("ArrowUp", VirtualKey::Up),
+    ("ArrowDown", VirtualKey::Down),
+    ("ArrowLeft", VirtualKey::Left),
+    ("ArrowRight", VirtualKey::Right),
+    // The main-row plus key, which is `Equal` with Shift on US layouts. The numpad keys are
+    // only parsed by their canonical names.
+    ("Plus", VirtualKey::Equal),
+    ("+", VirtualKey::Equal),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_key_names_round_trip() {
+        for vkey in VirtualKey::ALL {
+            let name = vkey.name().unwrap();
+            assert!(vkey.to_string() == name);
+            assert!(name.parse::<VirtualKey>() == Ok(vkey));
+            assert!(name.to_ascii_lowercase().parse::<VirtualKey>() == Ok(vkey));
+        }
+        assert!(VirtualKey::Other(42).to_string() == "Other(42)");
+        assert!("other(42)".parse::<VirtualKey>() == Ok(VirtualKey::Other(42)));
+        assert!(VirtualKey::Other(42).name().is_none());
+    }
+
+    #[test]
+    fn parse_virtual_key() {
+        assert!("return".parse::<VirtualKey>() == Ok(VirtualKey::Enter));
+        assert!("ESCAPE".parse::<VirtualKey>() == Ok(VirtualKey::Esc));
+        assert!("esc".parse::<VirtualKey>() == Ok(VirtualKey::Esc));
+        assert!("1".parse::<VirtualKey>() == Ok(VirtualKey::Key1));
+        assert!("a".parse::<VirtualKey>() == Ok(VirtualKey::A));
+        assert!("/".parse::<VirtualKey>() == Ok(VirtualKey::Slash));
+        assert!("+".parse::<VirtualKey>() == Ok(VirtualKey::Equal));
+        assert!("plus".parse::<VirtualKey>() == Ok(VirtualKey::Equal));
+        assert!("*".parse::<VirtualKey>().is_err());
+        assert!("NumAdd".parse::<VirtualKey>() == Ok(VirtualKey::NumAdd));
+        assert!("Foo".parse::<VirtualKey>() == Err(ParseKeyError("Foo".to_string())));
+        assert!("Other(x)".parse::<VirtualKey>().is_err());
+        assert!("".parse::<VirtualKey>().is_err());
+    }
+}
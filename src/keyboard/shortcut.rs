@@ -0,0 +1,235 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ShortcutStyle {
+    /// `Ctrl+Shift+S`, `Ctrl+Meta+S`
+    Standard,
+    /// `Ctrl+Shift+S`, `Ctrl+Win+S`
+    Windows,
+    /// `Ctrl+Shift+S`, `Ctrl+Super+S`, for Linux and other Unix desktops.
+    Linux,
+    /// `⇧⌘S`
+    Mac,
+}
+
+impl ShortcutStyle {
+    #[inline]
+    pub fn platform() -> Self {
+        if cfg!(any(target_os = "macos", target_os = "ios")) {
+            Self::Mac
+        } else if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(all(unix, not(target_os = "android"))) {
+            Self::Linux
+        } else {
+            Self::Standard
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseShortcutError {
+    Empty,
+    UnknownModifier(String),
+    UnknownKey(String),
+}
+
+impl std::fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty shortcut"),
+            Self::UnknownModifier(s) => write!(f, "unknown modifier: {}", s),
+            Self::UnknownKey(s) => write!(f, "unknown key: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseShortcutError {}
+
+/// A keyboard accelerator such as `Ctrl+Shift+S`.
+///
+/// Only `Shift`, `Ctrl`, `Alt` and `Meta` take part; left/right detail and lock keys are dropped.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: VirtualKey,
+}
+
+impl Shortcut {
+    #[inline]
+    pub fn new(modifiers: impl Into<Modifiers>, key: VirtualKey) -> Self {
+        Self {
+            modifiers: modifiers.into().without_sides().without_locks(),
+            key,
+        }
+    }
+
+    /// Returns `true` if a key press of `key` while `modifiers` are held triggers this shortcut.
+    #[inline]
+    pub fn matches(&self, key: KeyCode, modifiers: Modifiers) -> bool {
+        self.key == key.vkey
+            && self.modifiers.without_sides().without_locks()
+                == modifiers.without_sides().without_locks()
+    }
+
+    #[inline]
+    pub fn format(&self, style: ShortcutStyle) -> String {
+        KeyLabels::new().shortcut(self, style)
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(ShortcutStyle::Standard))
+    }
+}
+
+impl std::str::FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = s.trim();
+        let mut modifiers = Modifiers::new();
+        // Mac style symbols come before the key without separators.
+        while let Some(c) = s.chars().next() {
+            let Some((m, _)) = MAC_MODIFIER_SYMBOLS.iter().find(|(_, symbol)| *symbol == c) else {
+                break;
+            };
+            modifiers |= *m;
+            s = s[c.len_utf8()..].trim_start();
+        }
+        if s.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        // A trailing "+" is the key itself, as in "Ctrl++".
+        let (rest, key) = match s.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None => match s.rsplit_once('+') {
+                Some((rest, key)) => (rest, key),
+                None => ("", s),
+            },
+        };
+        if !rest.is_empty() {
+            for token in rest.split('+') {
+                let token = token.trim();
+                let m = parse_modifier(token)
+                    .ok_or_else(|| ParseShortcutError::UnknownModifier(token.to_string()))?;
+                modifiers |= m;
+            }
+        }
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        let key = key
+            .parse()
+            .map_err(|_| ParseShortcutError::UnknownKey(key.to_string()))?;
+        Ok(Shortcut::new(modifiers, key))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Shortcut {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Shortcut {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+pub(super) const MAC_MODIFIER_SYMBOLS: [(Modifier, char); 4] = [
+    (Modifier::Ctrl, '⌃'),
+    (Modifier::Alt, '⌥'),
+    (Modifier::Shift, '⇧'),
+    (Modifier::Meta, '⌘'),
+];
+
+fn parse_modifier(s: &str) -> Option<Modifier> {
+    let m = match s.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" | "ctl" => Modifier::Ctrl,
+        "shift" => Modifier::Shift,
+        "alt" | "option" | "opt" => Modifier::Alt,
+        "meta" | "cmd" | "command" | "super" | "win" => Modifier::Meta,
+        "cmdorctrl" | "commandorcontrol" => {
+            if ShortcutStyle::platform() == ShortcutStyle::Mac {
+                Modifier::Meta
+            } else {
+                Modifier::Ctrl
+            }
+        }
+        _ => return None,
+    };
+    Some(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shortcut() {
+        let s: Shortcut = "Ctrl+Shift+S".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Ctrl | Modifier::Shift, VirtualKey::S));
+        let s: Shortcut = "cmd + alt + f12".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Meta | Modifier::Alt, VirtualKey::F12));
+        let s: Shortcut = "Ctrl+-".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Ctrl, VirtualKey::Minus));
+        let s: Shortcut = "Ctrl++".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Ctrl, VirtualKey::Equal));
+        let s: Shortcut = "Ctrl+NumAdd".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Ctrl, VirtualKey::NumAdd));
+        let s: Shortcut = "⇧⌘S".parse().unwrap();
+        assert!(s == Shortcut::new(Modifier::Meta | Modifier::Shift, VirtualKey::S));
+        let s: Shortcut = "Escape".parse().unwrap();
+        assert!(s == Shortcut::new(Modifiers::new(), VirtualKey::Esc));
+    }
+
+    #[test]
+    fn parse_shortcut_error() {
+        assert!("".parse::<Shortcut>() == Err(ParseShortcutError::Empty));
+        assert!("Ctrl+".parse::<Shortcut>() == Err(ParseShortcutError::Empty));
+        assert!(
+            "Hyper+S".parse::<Shortcut>()
+                == Err(ParseShortcutError::UnknownModifier("Hyper".to_string()))
+        );
+        assert!(
+            "Ctrl+Foo".parse::<Shortcut>()
+                == Err(ParseShortcutError::UnknownKey("Foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn format_shortcut() {
+        let s = Shortcut::new(Modifier::Shift | Modifier::Ctrl, VirtualKey::S);
+        assert!(s.format(ShortcutStyle::Standard) == "Ctrl+Shift+S");
+        assert!(s.to_string() == "Ctrl+Shift+S");
+        let s = Shortcut::new(Modifier::Shift | Modifier::Meta, VirtualKey::S);
+        assert!(s.format(ShortcutStyle::Mac) == "⇧⌘S");
+        let s = Shortcut::new(Modifier::Meta, VirtualKey::BackSpace);
+        assert!(s.format(ShortcutStyle::Mac) == "⌘⌫");
+        let s = Shortcut::new(Modifier::Alt, VirtualKey::Slash);
+        assert!(s.to_string().parse::<Shortcut>().unwrap() == s);
+    }
+
+    #[test]
+    fn shortcut_matches() {
+        let s: Shortcut = "Ctrl+S".parse().unwrap();
+        let key = KeyCode::new(VirtualKey::S, ScanCode(0x1f));
+        assert!(s.matches(key, Modifier::LCtrl | Modifier::NumLock));
+        assert!(!s.matches(key, Modifier::LCtrl | Modifier::Shift));
+        assert!(!s.matches(key, Modifiers::new()));
+    }
+}
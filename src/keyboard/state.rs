@@ -0,0 +1,144 @@
+use super::*;
+
+/// Tracks which keys are held from a stream of key events.
+///
+/// Held keys are identified by `ScanCode`, so that keys sharing a `VirtualKey` such as both
+/// Shift keys on Windows are tracked separately. Keys without a scan code fall back to the
+/// `VirtualKey`.
+#[derive(Clone, Default, Debug)]
+pub struct KeyboardState {
+    pressed: Vec<KeyCode>,
+}
+
+impl KeyboardState {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` when `key` is an auto-repeat, a `Pressed` event for a key that is already held.
+    pub fn update(&mut self, key: KeyCode, state: KeyState) -> bool {
+        let index = self.pressed.iter().position(|k| same_key(k, &key));
+        match (state, index) {
+            (KeyState::Pressed, Some(i)) => {
+                self.pressed[i] = key;
+                true
+            }
+            (KeyState::Pressed, None) => {
+                self.pressed.push(key);
+                false
+            }
+            (KeyState::Released, Some(i)) => {
+                self.pressed.remove(i);
+                false
+            }
+            (KeyState::Released, None) => false,
+        }
+    }
+
+    /// `Shift`, `Ctrl` and `Alt` also match either of their left and right keys.
+    pub fn is_pressed(&self, vkey: VirtualKey) -> bool {
+        self.pressed.iter().any(|k| {
+            k.vkey == vkey
+                || matches!(
+                    (vkey, k.vkey),
+                    (VirtualKey::Shift, VirtualKey::LShift | VirtualKey::RShift)
+                        | (VirtualKey::Ctrl, VirtualKey::LCtrl | VirtualKey::RCtrl)
+                        | (VirtualKey::Alt, VirtualKey::LAlt | VirtualKey::RAlt)
+                )
+        })
+    }
+
+    #[inline]
+    pub fn is_pressed_scan(&self, scan_code: ScanCode) -> bool {
+        scan_code != ScanCode(0) && self.pressed.iter().any(|k| k.scan_code == scan_code)
+    }
+
+    /// Modifiers from the held keys. Lock states are not tracked.
+    pub fn modifiers(&self) -> Modifiers {
+        self.pressed
+            .iter()
+            .filter_map(|k| Modifier::from_vkey(k.vkey))
+            .filter(|m| !matches!(m, Modifier::CapsLock | Modifier::NumLock))
+            .collect()
+    }
+
+    /// Held keys in the order they were pressed.
+    #[inline]
+    pub fn pressed(&self) -> &[KeyCode] {
+        &self.pressed
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pressed.is_empty()
+    }
+
+    /// Releases every held key, e.g. on focus loss, and returns them in the order they were pressed.
+    #[inline]
+    pub fn release_all(&mut self) -> Vec<KeyCode> {
+        std::mem::take(&mut self.pressed)
+    }
+}
+
+#[inline]
+fn same_key(a: &KeyCode, b: &KeyCode) -> bool {
+    if a.scan_code != ScanCode(0) && b.scan_code != ScanCode(0) {
+        a.scan_code == b.scan_code
+    } else {
+        a.vkey == b.vkey
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_state() {
+        let mut state = KeyboardState::new();
+        let rshift = KeyCode::new(VirtualKey::RShift, ScanCode(0x36));
+        let a = KeyCode::new(VirtualKey::A, ScanCode(0x1e));
+        assert!(!state.update(rshift, KeyState::Pressed));
+        assert!(!state.update(a, KeyState::Pressed));
+        assert!(state.update(a, KeyState::Pressed));
+        assert!(state.pressed().len() == 2);
+        assert!(state.is_pressed(VirtualKey::Shift));
+        assert!(state.is_pressed(VirtualKey::RShift));
+        assert!(!state.is_pressed(VirtualKey::LShift));
+        assert!(state.is_pressed_scan(ScanCode(0x1e)));
+        assert!(!state.is_pressed_scan(ScanCode(0)));
+        assert!(state.modifiers() == Modifiers::from(Modifier::RShift));
+        assert!(!state.update(a, KeyState::Released));
+        assert!(!state.is_pressed(VirtualKey::A));
+        assert!(!state.update(a, KeyState::Released));
+        assert!(!state.update(a, KeyState::Pressed));
+        let released = state.release_all();
+        assert!(released == [rshift, a]);
+        assert!(state.is_empty());
+        assert!(state.modifiers().is_empty());
+    }
+
+    #[test]
+    fn keys_sharing_vkey() {
+        let mut state = KeyboardState::new();
+        let lshift = KeyCode::new(VirtualKey::Shift, ScanCode(0x2a));
+        let rshift = KeyCode::new(VirtualKey::Shift, ScanCode(0x36));
+        assert!(!state.update(lshift, KeyState::Pressed));
+        assert!(!state.update(rshift, KeyState::Pressed));
+        assert!(state.pressed().len() == 2);
+        assert!(!state.update(lshift, KeyState::Released));
+        assert!(state.is_pressed(VirtualKey::Shift));
+        assert!(state.is_pressed_scan(ScanCode(0x36)));
+        assert!(!state.update(rshift, KeyState::Released));
+        assert!(state.is_empty());
+        let a = KeyCode::new(VirtualKey::Other(0), ScanCode(0x70));
+        let b = KeyCode::new(VirtualKey::Other(0), ScanCode(0x71));
+        state.update(a, KeyState::Pressed);
+        assert!(!state.update(b, KeyState::Pressed));
+        let c = KeyCode::new(VirtualKey::F13, ScanCode(0));
+        state.update(c, KeyState::Pressed);
+        assert!(state.update(c, KeyState::Pressed));
+        assert!(state.pressed().len() == 3);
+    }
+}
@@ -0,0 +1,541 @@
+use super::*;
+
+// Keysyms from X11/keysymdef.h.
+const XK_SPACE: u32 = 0x0020;
+const XK_APOSTROPHE: u32 = 0x0027;
+const XK_COMMA: u32 = 0x002c;
+const XK_MINUS: u32 = 0x002d;
+const XK_PERIOD: u32 = 0x002e;
+const XK_SLASH: u32 = 0x002f;
+const XK_0: u32 = 0x0030;
+const XK_1: u32 = 0x0031;
+const XK_2: u32 = 0x0032;
+const XK_3: u32 = 0x0033;
+const XK_4: u32 = 0x0034;
+const XK_5: u32 = 0x0035;
+const XK_6: u32 = 0x0036;
+const XK_7: u32 = 0x0037;
+const XK_8: u32 = 0x0038;
+const XK_9: u32 = 0x0039;
+const XK_SEMICOLON: u32 = 0x003b;
+const XK_EQUAL: u32 = 0x003d;
+const XK_UPPER_A: u32 = 0x0041;
+const XK_UPPER_B: u32 = 0x0042;
+const XK_UPPER_C: u32 = 0x0043;
+const XK_UPPER_D: u32 = 0x0044;
+const XK_UPPER_E: u32 = 0x0045;
+const XK_UPPER_F: u32 = 0x0046;
+const XK_UPPER_G: u32 = 0x0047;
+const XK_UPPER_H: u32 = 0x0048;
+const XK_UPPER_I: u32 = 0x0049;
+const XK_UPPER_J: u32 = 0x004a;
+const XK_UPPER_K: u32 = 0x004b;
+const XK_UPPER_L: u32 = 0x004c;
+const XK_UPPER_M: u32 = 0x004d;
+const XK_UPPER_N: u32 = 0x004e;
+const XK_UPPER_O: u32 = 0x004f;
+const XK_UPPER_P: u32 = 0x0050;
+const XK_UPPER_Q: u32 = 0x0051;
+const XK_UPPER_R: u32 = 0x0052;
+const XK_UPPER_S: u32 = 0x0053;
+const XK_UPPER_T: u32 = 0x0054;
+const XK_UPPER_U: u32 = 0x0055;
+const XK_UPPER_V: u32 = 0x0056;
+const XK_UPPER_W: u32 = 0x0057;
+const XK_UPPER_X: u32 = 0x0058;
+const XK_UPPER_Y: u32 = 0x0059;
+const XK_UPPER_Z: u32 = 0x005a;
+const XK_BRACKETLEFT: u32 = 0x005b;
+const XK_BACKSLASH: u32 = 0x005c;
+const XK_BRACKETRIGHT: u32 = 0x005d;
+const XK_GRAVE: u32 = 0x0060;
+const XK_A: u32 = 0x0061;
+const XK_B: u32 = 0x0062;
+const XK_C: u32 = 0x0063;
+const XK_D: u32 = 0x0064;
+const XK_E: u32 = 0x0065;
+const XK_F: u32 = 0x0066;
+const XK_G: u32 = 0x0067;
+const XK_H: u32 = 0x0068;
+const XK_I: u32 = 0x0069;
+const XK_J: u32 = 0x006a;
+const XK_K: u32 = 0x006b;
+const XK_L: u32 = 0x006c;
+const XK_M: u32 = 0x006d;
+const XK_N: u32 = 0x006e;
+const XK_O: u32 = 0x006f;
+const XK_P: u32 = 0x0070;
+const XK_Q: u32 = 0x0071;
+const XK_R: u32 = 0x0072;
+const XK_S: u32 = 0x0073;
+const XK_T: u32 = 0x0074;
+const XK_U: u32 = 0x0075;
+const XK_V: u32 = 0x0076;
+const XK_W: u32 = 0x0077;
+const XK_X: u32 = 0x0078;
+const XK_Y: u32 = 0x0079;
+const XK_Z: u32 = 0x007a;
+const XK_ISO_LEVEL3_SHIFT: u32 = 0xfe03;
+const XK_ISO_LEFT_TAB: u32 = 0xfe20;
+const XK_BACKSPACE: u32 = 0xff08;
+const XK_TAB: u32 = 0xff09;
+const XK_RETURN: u32 = 0xff0d;
+const XK_PAUSE: u32 = 0xff13;
+const XK_SCROLL_LOCK: u32 = 0xff14;
+const XK_ESCAPE: u32 = 0xff1b;
+const XK_HOME: u32 = 0xff50;
+const XK_LEFT: u32 = 0xff51;
+const XK_UP: u32 = 0xff52;
+const XK_RIGHT: u32 = 0xff53;
+const XK_DOWN: u32 = 0xff54;
+const XK_PAGE_UP: u32 = 0xff55;
+const XK_PAGE_DOWN: u32 = 0xff56;
+const XK_END: u32 = 0xff57;
+const XK_PRINT: u32 = 0xff61;
+const XK_INSERT: u32 = 0xff63;
+const XK_MENU: u32 = 0xff67;
+const XK_NUM_LOCK: u32 = 0xff7f;
+const XK_KP_ENTER: u32 = 0xff8d;
+const XK_KP_HOME: u32 = 0xff95;
+const XK_KP_LEFT: u32 = 0xff96;
+const XK_KP_UP: u32 = 0xff97;
+const XK_KP_RIGHT: u32 = 0xff98;
+const XK_KP_DOWN: u32 = 0xff99;
+const XK_KP_PAGE_UP: u32 = 0xff9a;
+const XK_KP_PAGE_DOWN: u32 = 0xff9b;
+const XK_KP_END: u32 = 0xff9c;
+const XK_KP_INSERT: u32 = 0xff9e;
+const XK_KP_DELETE: u32 = 0xff9f;
+const XK_KP_MULTIPLY: u32 = 0xffaa;
+const XK_KP_ADD: u32 = 0xffab;
+const XK_KP_SUBTRACT: u32 = 0xffad;
+const XK_KP_DECIMAL: u32 = 0xffae;
+const XK_KP_DIVIDE: u32 = 0xffaf;
+const XK_KP_0: u32 = 0xffb0;
+const XK_KP_1: u32 = 0xffb1;
+const XK_KP_2: u32 = 0xffb2;
+const XK_KP_3: u32 = 0xffb3;
+const XK_KP_4: u32 = 0xffb4;
+const XK_KP_5: u32 = 0xffb5;
+const XK_KP_6: u32 = 0xffb6;
+const XK_KP_7: u32 = 0xffb7;
+const XK_KP_8: u32 = 0xffb8;
+const XK_KP_9: u32 = 0xffb9;
+const XK_F1: u32 = 0xffbe;
+const XK_F2: u32 = 0xffbf;
+const XK_F3: u32 = 0xffc0;
+const XK_F4: u32 = 0xffc1;
+const XK_F5: u32 = 0xffc2;
+const XK_F6: u32 = 0xffc3;
+const XK_F7: u32 = 0xffc4;
+const XK_F8: u32 = 0xffc5;
+const XK_F9: u32 = 0xffc6;
+const XK_F10: u32 = 0xffc7;
+const XK_F11: u32 = 0xffc8;
+const XK_F12: u32 = 0xffc9;
+const XK_F13: u32 = 0xffca;
+const XK_F14: u32 = 0xffcb;
+const XK_F15: u32 = 0xffcc;
+const XK_F16: u32 = 0xffcd;
+const XK_F17: u32 = 0xffce;
+const XK_F18: u32 = 0xffcf;
+const XK_F19: u32 = 0xffd0;
+const XK_F20: u32 = 0xffd1;
+const XK_F21: u32 = 0xffd2;
+const XK_F22: u32 = 0xffd3;
+const XK_F23: u32 = 0xffd4;
+const XK_F24: u32 = 0xffd5;
+const XK_SHIFT_L: u32 = 0xffe1;
+const XK_SHIFT_R: u32 = 0xffe2;
+const XK_CONTROL_L: u32 = 0xffe3;
+const XK_CONTROL_R: u32 = 0xffe4;
+const XK_CAPS_LOCK: u32 = 0xffe5;
+const XK_META_L: u32 = 0xffe7;
+const XK_META_R: u32 = 0xffe8;
+const XK_ALT_L: u32 = 0xffe9;
+const XK_ALT_R: u32 = 0xffea;
+const XK_SUPER_L: u32 = 0xffeb;
+const XK_SUPER_R: u32 = 0xffec;
+const XK_DELETE: u32 = 0xffff;
+
+// Keysyms from X11/XF86keysym.h.
+const XF86XK_AUDIO_LOWER_VOLUME: u32 = 0x1008ff11;
+const XF86XK_AUDIO_MUTE: u32 = 0x1008ff12;
+const XF86XK_AUDIO_RAISE_VOLUME: u32 = 0x1008ff13;
+const XF86XK_AUDIO_PLAY: u32 = 0x1008ff14;
+const XF86XK_AUDIO_STOP: u32 = 0x1008ff15;
+const XF86XK_AUDIO_PREV: u32 = 0x1008ff16;
+const XF86XK_AUDIO_NEXT: u32 = 0x1008ff17;
+const XF86XK_HOME_PAGE: u32 = 0x1008ff18;
+const XF86XK_MAIL: u32 = 0x1008ff19;
+const XF86XK_SEARCH: u32 = 0x1008ff1b;
+const XF86XK_CALCULATOR: u32 = 0x1008ff1d;
+const XF86XK_BACK: u32 = 0x1008ff26;
+const XF86XK_FORWARD: u32 = 0x1008ff27;
+const XF86XK_STOP: u32 = 0x1008ff28;
+const XF86XK_REFRESH: u32 = 0x1008ff29;
+const XF86XK_FAVORITES: u32 = 0x1008ff30;
+const XF86XK_AUDIO_PAUSE: u32 = 0x1008ff31;
+const XF86XK_AUDIO_MEDIA: u32 = 0x1008ff32;
+const XF86XK_MY_COMPUTER: u32 = 0x1008ff33;
+
+impl VirtualKey {
+    /// Keysyms without a variant give `Other(0)`.
+    pub fn from_keysym(keysym: u32) -> Self {
+        match keysym {
+            XK_ESCAPE => VirtualKey::Esc,
+            XK_TAB | XK_ISO_LEFT_TAB => VirtualKey::Tab,
+            XK_CAPS_LOCK => VirtualKey::CapsLock,
+            XK_SHIFT_L => VirtualKey::LShift,
+            XK_SHIFT_R => VirtualKey::RShift,
+            XK_CONTROL_L => VirtualKey::LCtrl,
+            XK_CONTROL_R => VirtualKey::RCtrl,
+            XK_ALT_L => VirtualKey::LAlt,
+            XK_ALT_R | XK_ISO_LEVEL3_SHIFT => VirtualKey::RAlt,
+            XK_SUPER_L | XK_META_L => VirtualKey::LMeta,
+            XK_SUPER_R | XK_META_R => VirtualKey::RMeta,
+            XK_MENU => VirtualKey::ContextMenu,
+            XK_BACKSPACE => VirtualKey::BackSpace,
+            XK_RETURN => VirtualKey::Enter,
+            XK_SPACE => VirtualKey::Space,
+            XK_PRINT => VirtualKey::PrintScreen,
+            XK_SCROLL_LOCK => VirtualKey::ScrollLock,
+            XK_PAUSE => VirtualKey::Pause,
+            XK_INSERT | XK_KP_INSERT => VirtualKey::Insert,
+            XK_DELETE | XK_KP_DELETE => VirtualKey::Delete,
+            XK_HOME | XK_KP_HOME => VirtualKey::Home,
+            XK_END | XK_KP_END => VirtualKey::End,
+            XK_PAGE_UP | XK_KP_PAGE_UP => VirtualKey::PageUp,
+            XK_PAGE_DOWN | XK_KP_PAGE_DOWN => VirtualKey::PageDown,
+            XK_UP | XK_KP_UP => VirtualKey::Up,
+            XK_DOWN | XK_KP_DOWN => VirtualKey::Down,
+            XK_LEFT | XK_KP_LEFT => VirtualKey::Left,
+            XK_RIGHT | XK_KP_RIGHT => VirtualKey::Right,
+            XK_1 => VirtualKey::Key1,
+            XK_2 => VirtualKey::Key2,
+            XK_3 => VirtualKey::Key3,
+            XK_4 => VirtualKey::Key4,
+            XK_5 => VirtualKey::Key5,
+            XK_6 => VirtualKey::Key6,
+            XK_7 => VirtualKey::Key7,
+            XK_8 => VirtualKey::Key8,
+            XK_9 => VirtualKey::Key9,
+            XK_0 => VirtualKey::Key0,
+            XK_A | XK_UPPER_A => VirtualKey::A,
+            XK_B | XK_UPPER_B => VirtualKey::B,
+            XK_C | XK_UPPER_C => VirtualKey::C,
+            XK_D | XK_UPPER_D => VirtualKey::D,
+            XK_E | XK_UPPER_E => VirtualKey::E,
+            XK_F | XK_UPPER_F => VirtualKey::F,
+            XK_G | XK_UPPER_G => VirtualKey::G,
+            XK_H | XK_UPPER_H => VirtualKey::H,
+            XK_I | XK_UPPER_I => VirtualKey::I,
+            XK_J | XK_UPPER_J => VirtualKey::J,
+            XK_K | XK_UPPER_K => VirtualKey::K,
+            XK_L | XK_UPPER_L => VirtualKey::L,
+            XK_M | XK_UPPER_M => VirtualKey::M,
+            XK_N | XK_UPPER_N => VirtualKey::N,
+            XK_O | XK_UPPER_O => VirtualKey::O,
+            XK_P | XK_UPPER_P => VirtualKey::P,
+            XK_Q | XK_UPPER_Q => VirtualKey::Q,
+            XK_R | XK_UPPER_R => VirtualKey::R,
+            XK_S | XK_UPPER_S => VirtualKey::S,
+            XK_T | XK_UPPER_T => VirtualKey::T,
+            XK_U | XK_UPPER_U => VirtualKey::U,
+            XK_V | XK_UPPER_V => VirtualKey::V,
+            XK_W | XK_UPPER_W => VirtualKey::W,
+            XK_X | XK_UPPER_X => VirtualKey::X,
+            XK_Y | XK_UPPER_Y => VirtualKey::Y,
+            XK_Z | XK_UPPER_Z => VirtualKey::Z,
+            XK_SEMICOLON => VirtualKey::Semicolon,
+            XK_EQUAL => VirtualKey::Equal,
+            XK_COMMA => VirtualKey::Comma,
+            XK_MINUS => VirtualKey::Minus,
+            XK_PERIOD => VirtualKey::Period,
+            XK_SLASH => VirtualKey::Slash,
+            XK_GRAVE => VirtualKey::Backquote,
+            XK_BRACKETLEFT => VirtualKey::LBracket,
+            XK_BACKSLASH => VirtualKey::Backslash,
+            XK_BRACKETRIGHT => VirtualKey::RBracket,
+            XK_APOSTROPHE => VirtualKey::Quote,
+            XK_F1 => VirtualKey::F1,
+            XK_F2 => VirtualKey::F2,
+            XK_F3 => VirtualKey::F3,
+            XK_F4 => VirtualKey::F4,
+            XK_F5 => VirtualKey::F5,
+            XK_F6 => VirtualKey::F6,
+            XK_F7 => VirtualKey::F7,
+            XK_F8 => VirtualKey::F8,
+            XK_F9 => VirtualKey::F9,
+            XK_F10 => VirtualKey::F10,
+            XK_F11 => VirtualKey::F11,
+            XK_F12 => VirtualKey::F12,
+            XK_F13 => VirtualKey::F13,
+            XK_F14 => VirtualKey::F14,
+            XK_F15 => VirtualKey::F15,
+            XK_F16 => VirtualKey::F16,
+            XK_F17 => VirtualKey::F17,
+            XK_F18 => VirtualKey::F18,
+            XK_F19 => VirtualKey::F19,
+            XK_F20 => VirtualKey::F20,
+            XK_F21 => VirtualKey::F21,
+            XK_F22 => VirtualKey::F22,
+            XK_F23 => VirtualKey::F23,
+            XK_F24 => VirtualKey::F24,
+            XK_NUM_LOCK => VirtualKey::NumLock,
+            XK_KP_1 => VirtualKey::Num1,
+            XK_KP_2 => VirtualKey::Num2,
+            XK_KP_3 => VirtualKey::Num3,
+            XK_KP_4 => VirtualKey::Num4,
+            XK_KP_5 => VirtualKey::Num5,
+            XK_KP_6 => VirtualKey::Num6,
+            XK_KP_7 => VirtualKey::Num7,
+            XK_KP_8 => VirtualKey::Num8,
+            XK_KP_9 => VirtualKey::Num9,
+            XK_KP_0 => VirtualKey::Num0,
+            XK_KP_ADD => VirtualKey::NumAdd,
+            XK_KP_SUBTRACT => VirtualKey::NumSub,
+            XK_KP_MULTIPLY => VirtualKey::NumMul,
+            XK_KP_DIVIDE => VirtualKey::NumDiv,
+            XK_KP_DECIMAL => VirtualKey::NumDecimal,
+            XK_KP_ENTER => VirtualKey::NumEnter,
+            XF86XK_AUDIO_MUTE => VirtualKey::VolumeMute,
+            XF86XK_AUDIO_LOWER_VOLUME => VirtualKey::VolumeDown,
+            XF86XK_AUDIO_RAISE_VOLUME => VirtualKey::VolumeUp,
+            XF86XK_AUDIO_NEXT => VirtualKey::MediaNextTrack,
+            XF86XK_AUDIO_PREV => VirtualKey::MediaPrevTrack,
+            XF86XK_AUDIO_STOP => VirtualKey::MediaStop,
+            XF86XK_AUDIO_PLAY | XF86XK_AUDIO_PAUSE => VirtualKey::MediaPlayPause,
+            XF86XK_BACK => VirtualKey::BrowserBack,
+            XF86XK_FORWARD => VirtualKey::BrowserForward,
+            XF86XK_REFRESH => VirtualKey::BrowserRefresh,
+            XF86XK_STOP => VirtualKey::BrowserStop,
+            XF86XK_SEARCH => VirtualKey::BrowserSearch,
+            XF86XK_FAVORITES => VirtualKey::BrowserFavorites,
+            XF86XK_HOME_PAGE => VirtualKey::BrowserHome,
+            XF86XK_MAIL => VirtualKey::LaunchMail,
+            XF86XK_AUDIO_MEDIA => VirtualKey::LaunchMediaSelect,
+            XF86XK_MY_COMPUTER => VirtualKey::LaunchApp1,
+            XF86XK_CALCULATOR => VirtualKey::LaunchApp2,
+            _ => VirtualKey::Other(0),
+        }
+    }
+
+    pub fn to_keysym(&self) -> Option<u32> {
+        let keysym = match self {
+            VirtualKey::Esc => XK_ESCAPE,
+            VirtualKey::Tab => XK_TAB,
+            VirtualKey::CapsLock => XK_CAPS_LOCK,
+            VirtualKey::Shift => XK_SHIFT_L,
+            VirtualKey::Ctrl => XK_CONTROL_L,
+            VirtualKey::Alt => XK_ALT_L,
+            VirtualKey::LShift => XK_SHIFT_L,
+            VirtualKey::RShift => XK_SHIFT_R,
+            VirtualKey::LCtrl => XK_CONTROL_L,
+            VirtualKey::RCtrl => XK_CONTROL_R,
+            VirtualKey::LAlt => XK_ALT_L,
+            VirtualKey::RAlt => XK_ALT_R,
+            VirtualKey::LMeta => XK_SUPER_L,
+            VirtualKey::RMeta => XK_SUPER_R,
+            VirtualKey::ContextMenu => XK_MENU,
+            VirtualKey::BackSpace => XK_BACKSPACE,
+            VirtualKey::Enter => XK_RETURN,
+            VirtualKey::Space => XK_SPACE,
+            VirtualKey::PrintScreen => XK_PRINT,
+            VirtualKey::ScrollLock => XK_SCROLL_LOCK,
+            VirtualKey::Pause => XK_PAUSE,
+            VirtualKey::Insert => XK_INSERT,
+            VirtualKey::Delete => XK_DELETE,
+            VirtualKey::Home => XK_HOME,
+            VirtualKey::End => XK_END,
+            VirtualKey::PageUp => XK_PAGE_UP,
+            VirtualKey::PageDown => XK_PAGE_DOWN,
+            VirtualKey::Up => XK_UP,
+            VirtualKey::Down => XK_DOWN,
+            VirtualKey::Left => XK_LEFT,
+            VirtualKey::Right => XK_RIGHT,
+            VirtualKey::Key1 => XK_1,
+            VirtualKey::Key2 => XK_2,
+            VirtualKey::Key3 => XK_3,
+            VirtualKey::Key4 => XK_4,
+            VirtualKey::Key5 => XK_5,
+            VirtualKey::Key6 => XK_6,
+            VirtualKey::Key7 => XK_7,
+            VirtualKey::Key8 => XK_8,
+            VirtualKey::Key9 => XK_9,
+            VirtualKey::Key0 => XK_0,
+            VirtualKey::A => XK_A,
+            VirtualKey::B => XK_B,
+            VirtualKey::C => XK_C,
+            VirtualKey::D => XK_D,
+            VirtualKey::E => XK_E,
+            VirtualKey::F => XK_F,
+            VirtualKey::G => XK_G,
+            VirtualKey::H => XK_H,
+            VirtualKey::I => XK_I,
+            VirtualKey::J => XK_J,
+            VirtualKey::K => XK_K,
+            VirtualKey::L => XK_L,
+            VirtualKey::M => XK_M,
+            VirtualKey::N => XK_N,
+            VirtualKey::O => XK_O,
+            VirtualKey::P => XK_P,
+            VirtualKey::Q => XK_Q,
+            VirtualKey::R => XK_R,
+            VirtualKey::S => XK_S,
+            VirtualKey::T => XK_T,
+            VirtualKey::U => XK_U,
+            VirtualKey::V => XK_V,
+            VirtualKey::W => XK_W,
+            VirtualKey::X => XK_X,
+            VirtualKey::Y => XK_Y,
+            VirtualKey::Z => XK_Z,
+            VirtualKey::Semicolon => XK_SEMICOLON,
+            VirtualKey::Equal => XK_EQUAL,
+            VirtualKey::Comma => XK_COMMA,
+            VirtualKey::Minus => XK_MINUS,
+            VirtualKey::Period => XK_PERIOD,
+            VirtualKey::Slash => XK_SLASH,
+            VirtualKey::Backquote => XK_GRAVE,
+            VirtualKey::LBracket => XK_BRACKETLEFT,
+            VirtualKey::Backslash => XK_BACKSLASH,
+            VirtualKey::RBracket => XK_BRACKETRIGHT,
+            VirtualKey::Quote => XK_APOSTROPHE,
+            // The keysym depends on the layout, e.g. `less` on German or `backslash` on UK keyboards.
+            VirtualKey::IntlBackslash => return None,
+            VirtualKey::F1 => XK_F1,
+            VirtualKey::F2 => XK_F2,
+            VirtualKey::F3 => XK_F3,
+            VirtualKey::F4 => XK_F4,
+            VirtualKey::F5 => XK_F5,
+            VirtualKey::F6 => XK_F6,
+            VirtualKey::F7 => XK_F7,
+            VirtualKey::F8 => XK_F8,
+            VirtualKey::F9 => XK_F9,
+            VirtualKey::F10 => XK_F10,
+            VirtualKey::F11 => XK_F11,
+            VirtualKey::F12 => XK_F12,
+            VirtualKey::F13 => XK_F13,
+            VirtualKey::F14 => XK_F14,
+            VirtualKey::F15 => XK_F15,
+            VirtualKey::F16 => XK_F16,
+            VirtualKey::F17 => XK_F17,
+            VirtualKey::F18 => XK_F18,
+            VirtualKey::F19 => XK_F19,
+            VirtualKey::F20 => XK_F20,
+            VirtualKey::F21 => XK_F21,
+            VirtualKey::F22 => XK_F22,
+            VirtualKey::F23 => XK_F23,
+            VirtualKey::F24 => XK_F24,
+            VirtualKey::NumLock => XK_NUM_LOCK,
+            VirtualKey::Num1 => XK_KP_1,
+            VirtualKey::Num2 => XK_KP_2,
+            VirtualKey::Num3 => XK_KP_3,
+            VirtualKey::Num4 => XK_KP_4,
+            VirtualKey::Num5 => XK_KP_5,
+            VirtualKey::Num6 => XK_KP_6,
+            VirtualKey::Num7 => XK_KP_7,
+            VirtualKey::Num8 => XK_KP_8,
+            VirtualKey::Num9 => XK_KP_9,
+            VirtualKey::Num0 => XK_KP_0,
+            VirtualKey::NumAdd => XK_KP_ADD,
+            VirtualKey::NumSub => XK_KP_SUBTRACT,
+            VirtualKey::NumMul => XK_KP_MULTIPLY,
+            VirtualKey::NumDiv => XK_KP_DIVIDE,
+            VirtualKey::NumDecimal => XK_KP_DECIMAL,
+            VirtualKey::NumEnter => XK_KP_ENTER,
+            VirtualKey::VolumeMute => XF86XK_AUDIO_MUTE,
+            VirtualKey::VolumeDown => XF86XK_AUDIO_LOWER_VOLUME,
+            VirtualKey::VolumeUp => XF86XK_AUDIO_RAISE_VOLUME,
+            VirtualKey::MediaNextTrack => XF86XK_AUDIO_NEXT,
+            VirtualKey::MediaPrevTrack => XF86XK_AUDIO_PREV,
+            VirtualKey::MediaStop => XF86XK_AUDIO_STOP,
+            VirtualKey::MediaPlayPause => XF86XK_AUDIO_PLAY,
+            VirtualKey::BrowserBack => XF86XK_BACK,
+            VirtualKey::BrowserForward => XF86XK_FORWARD,
+            VirtualKey::BrowserRefresh => XF86XK_REFRESH,
+            VirtualKey::BrowserStop => XF86XK_STOP,
+            VirtualKey::BrowserSearch => XF86XK_SEARCH,
+            VirtualKey::BrowserFavorites => XF86XK_FAVORITES,
+            VirtualKey::BrowserHome => XF86XK_HOME_PAGE,
+            VirtualKey::LaunchMail => XF86XK_MAIL,
+            VirtualKey::LaunchMediaSelect => XF86XK_AUDIO_MEDIA,
+            VirtualKey::LaunchApp1 => XF86XK_MY_COMPUTER,
+            VirtualKey::LaunchApp2 => XF86XK_CALCULATOR,
+            VirtualKey::Other(_) => return None,
+        };
+        Some(keysym)
+    }
+}
+
+const XKB_KEYCODE_OFFSET: u32 = 8;
+
+impl ScanCode {
+    #[inline]
+    pub fn from_xkb_keycode(keycode: u32) -> Option<Self> {
+        keycode
+            .checked_sub(XKB_KEYCODE_OFFSET)
+            .and_then(ScanCode::from_evdev)
+    }
+
+    #[inline]
+    pub fn to_xkb_keycode(&self) -> Option<u32> {
+        self.to_evdev().map(|code| code + XKB_KEYCODE_OFFSET)
+    }
+}
+
+impl KeyCode {
+    #[inline]
+    pub fn from_xkb_keycode(keycode: u32) -> Self {
+        match keycode.checked_sub(XKB_KEYCODE_OFFSET) {
+            Some(code) => KeyCode::from_evdev(code),
+            None => KeyCode::new(VirtualKey::Other(0), ScanCode(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keysym_to_vkey() {
+        assert!(VirtualKey::from_keysym(XK_RETURN) == VirtualKey::Enter);
+        assert!(VirtualKey::from_keysym(XK_KP_ADD) == VirtualKey::NumAdd);
+        assert!(VirtualKey::from_keysym(XK_A) == VirtualKey::A);
+        assert!(VirtualKey::from_keysym(XK_UPPER_A) == VirtualKey::A);
+        assert!(VirtualKey::from_keysym(XK_KP_HOME) == VirtualKey::Home);
+        assert!(VirtualKey::from_keysym(XK_KP_ENTER) == VirtualKey::NumEnter);
+        assert!(VirtualKey::from_keysym(XK_SUPER_L) == VirtualKey::LMeta);
+        assert!(VirtualKey::from_keysym(XK_SEMICOLON) == VirtualKey::Semicolon);
+        assert!(VirtualKey::from_keysym(XF86XK_AUDIO_MUTE) == VirtualKey::VolumeMute);
+        assert!(VirtualKey::from_keysym(0x1008ff2c) == VirtualKey::Other(0));
+        assert!(VirtualKey::Other(0x1008ff2c).to_keysym().is_none());
+    }
+
+    #[test]
+    fn vkey_keysym_round_trip() {
+        for keysym in (0x20..0x7f)
+            .chain(0xfe00..0x10000)
+            .chain(0x1008ff00..0x1008ff80)
+        {
+            let vkey = VirtualKey::from_keysym(keysym);
+            if let VirtualKey::Other(_) = vkey {
+                continue;
+            }
+            let keysym = vkey.to_keysym().unwrap();
+            assert!(VirtualKey::from_keysym(keysym) == vkey);
+        }
+    }
+
+    #[test]
+    fn xkb_keycode() {
+        assert!(ScanCode::from_xkb_keycode(38) == Some(ScanCode(0x1E)));
+        assert!(ScanCode(0xE048).to_xkb_keycode() == Some(111));
+        assert!(ScanCode::from_xkb_keycode(3).is_none());
+        assert!(KeyCode::from_xkb_keycode(3).vkey == VirtualKey::Other(0));
+        let key = KeyCode::from_xkb_keycode(105);
+        assert!(key.vkey == VirtualKey::RCtrl);
+        assert!(key.scan_code == ScanCode(0xE01D));
+    }
+}
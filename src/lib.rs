@@ -1,9 +1,25 @@
 pub mod collision;
+pub mod event;
+pub mod gamepad;
 pub mod geometry;
 pub mod keyboard;
 pub mod mouse;
+pub mod pen;
+pub mod pointer;
+#[cfg(feature = "record")]
+pub mod record;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text_input;
 
 pub use collision::*;
+pub use event::*;
+pub use gamepad::*;
 pub use geometry::*;
 pub use keyboard::*;
 pub use mouse::*;
+pub use pen::*;
+pub use pointer::*;
+#[cfg(feature = "record")]
+pub use record::*;
+pub use text_input::*;
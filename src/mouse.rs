@@ -1,12 +1,27 @@
 use super::*;
 
 #[cfg(windows)]
-use windows::Win32::{Foundation::WPARAM, System::SystemServices::*};
+use windows::Win32::Foundation::WPARAM;
+
+mod state;
+mod wheel;
+
+pub use state::*;
+pub use wheel::*;
 
 pub type ButtonState = KeyState;
 
+mod mk {
+    pub const LBUTTON: u32 = 0x0001;
+    pub const RBUTTON: u32 = 0x0002;
+    pub const SHIFT: u32 = 0x0004;
+    pub const CONTROL: u32 = 0x0008;
+    pub const MBUTTON: u32 = 0x0010;
+    pub const XBUTTON1: u32 = 0x0020;
+    pub const XBUTTON2: u32 = 0x0040;
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     Left,
     Right,
@@ -25,24 +40,176 @@ impl MouseButton {
             Self::Ex(x) => 0x01 << (x + 3),
         }
     }
+
+    fn from_index(index: u32) -> Self {
+        match index {
+            0 => Self::Left,
+            1 => Self::Right,
+            2 => Self::Middle,
+            x => Self::Ex(x - 3),
+        }
+    }
 }
 
+/// `Left`, `Right`, `Middle`, and `X1` to `X29` for `Ex(0)` to `Ex(28)`.
 impl std::fmt::Display for MouseButton {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::Left => write!(f, "Left"),
+            Self::Right => write!(f, "Right"),
+            Self::Middle => write!(f, "Middle"),
+            Self::Ex(x) => write!(f, "X{}", x + 1),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseMouseButtonError(pub String);
+
+impl std::fmt::Display for ParseMouseButtonError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown mouse button: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseMouseButtonError {}
+
+/// Parses the `Display` names case-insensitively, plus `Back` and `Forward` for `X1` and `X2`.
+impl std::str::FromStr for MouseButton {
+    type Err = ParseMouseButtonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        let button = match lower.as_str() {
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "middle" => Some(Self::Middle),
+            "back" => Some(Self::Ex(0)),
+            "forward" => Some(Self::Ex(1)),
+            _ => lower
+                .strip_prefix('x')
+                .and_then(|n| n.parse::<u32>().ok())
+                .filter(|n| (1..=Self::EX_LEN).contains(n))
+                .map(|n| Self::Ex(n - 1)),
+        };
+        button.ok_or_else(|| ParseMouseButtonError(s.to_string()))
+    }
+}
+
+/// The derived form, such as `{"Ex":1}`, that `MouseButton` was written in before it had names.
+/// Non-human-readable formats still use it.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "MouseButton")]
+enum MouseButtonRepr {
+    Left,
+    Right,
+    Middle,
+    Ex(u32),
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum MouseButtonHumanReadable {
+    Name(String),
+    Repr(MouseButtonRepr),
+}
+
+/// Human-readable formats get the `Display` name.
+#[cfg(feature = "serde")]
+impl serde::Serialize for MouseButton {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        let repr = match *self {
+            Self::Left => MouseButtonRepr::Left,
+            Self::Right => MouseButtonRepr::Right,
+            Self::Middle => MouseButtonRepr::Middle,
+            Self::Ex(x) => MouseButtonRepr::Ex(x),
+        };
+        repr.serialize(serializer)
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// Accepts both the name and the derived form, and rejects `Ex(n)` out of range, since the
+/// button sets cannot hold it.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MouseButton {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let repr = if deserializer.is_human_readable() {
+            match MouseButtonHumanReadable::deserialize(deserializer)? {
+                MouseButtonHumanReadable::Name(s) => {
+                    return s.parse().map_err(serde::de::Error::custom);
+                }
+                MouseButtonHumanReadable::Repr(repr) => repr,
+            }
+        } else {
+            MouseButtonRepr::deserialize(deserializer)?
+        };
+        match repr {
+            MouseButtonRepr::Left => Ok(Self::Left),
+            MouseButtonRepr::Right => Ok(Self::Right),
+            MouseButtonRepr::Middle => Ok(Self::Middle),
+            MouseButtonRepr::Ex(x) if x < Self::EX_LEN => Ok(Self::Ex(x)),
+            MouseButtonRepr::Ex(x) => Err(serde::de::Error::custom(format!(
+                "unknown mouse button: Ex({})",
+                x
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MouseButtons(u32);
 
 impl MouseButtons {
+    #[cfg(feature = "record")]
+    const ALL: u32 = u32::MAX >> (32 - 3 - MouseButton::EX_LEN);
+
     #[inline]
     pub fn new() -> Self {
         Self(0)
     }
 
+    #[cfg(feature = "record")]
+    #[inline]
+    pub(crate) fn bits(&self) -> u32 {
+        self.0
+    }
+
+    #[cfg(feature = "record")]
+    #[inline]
+    pub(crate) fn from_bits(bits: u32) -> Self {
+        Self(bits & Self::ALL)
+    }
+
+    /// Decodes the `MK_*` key state flags of Windows mouse messages.
+    pub fn from_mk_flags(flags: u32) -> Self {
+        let mut buttons = Self::new();
+        for (flag, button) in [
+            (mk::LBUTTON, MouseButton::Left),
+            (mk::RBUTTON, MouseButton::Right),
+            (mk::MBUTTON, MouseButton::Middle),
+            (mk::XBUTTON1, MouseButton::Ex(0)),
+            (mk::XBUTTON2, MouseButton::Ex(1)),
+        ] {
+            if flags & flag != 0 {
+                buttons |= button;
+            }
+        }
+        buttons
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.0 == 0
@@ -54,23 +221,29 @@ impl MouseButtons {
         self.0 & button == button
     }
 
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[inline]
+    pub fn insert(&mut self, button: MouseButton) {
+        self.0 |= button.as_u32();
+    }
+
+    #[inline]
+    pub fn remove(&mut self, button: MouseButton) {
+        self.0 &= !button.as_u32();
+    }
+
+    #[inline]
+    pub fn iter(&self) -> MouseButtonsIter {
+        MouseButtonsIter(self.0)
+    }
+
+    #[inline]
     pub fn to_vec(&self) -> Vec<MouseButton> {
-        let mut v = vec![];
-        if self.contains(MouseButton::Left) {
-            v.push(MouseButton::Left);
-        }
-        if self.contains(MouseButton::Right) {
-            v.push(MouseButton::Right);
-        }
-        if self.contains(MouseButton::Middle) {
-            v.push(MouseButton::Middle);
-        }
-        for i in 0..MouseButton::EX_LEN {
-            if self.contains(MouseButton::Ex(i)) {
-                v.push(MouseButton::Ex(i));
-            }
-        }
-        v
+        self.iter().collect()
     }
 }
 
@@ -96,6 +269,57 @@ impl std::fmt::Display for MouseButtons {
     }
 }
 
+pub struct MouseButtonsIter(u32);
+
+impl Iterator for MouseButtonsIter {
+    type Item = MouseButton;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(MouseButton::from_index(index))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for MouseButtonsIter {}
+
+impl IntoIterator for MouseButtons {
+    type Item = MouseButton;
+    type IntoIter = MouseButtonsIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &MouseButtons {
+    type Item = MouseButton;
+    type IntoIter = MouseButtonsIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<MouseButton> for MouseButtons {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = MouseButton>>(iter: I) -> Self {
+        iter.into_iter().fold(MouseButtons::new(), |r, b| r | b)
+    }
+}
+
 impl std::ops::BitOr for MouseButton {
     type Output = MouseButtons;
 
@@ -123,6 +347,15 @@ impl std::ops::BitOr<MouseButton> for MouseButtons {
     }
 }
 
+impl std::ops::BitOr for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        MouseButtons(self.0 | rhs.0)
+    }
+}
+
 impl std::ops::BitOrAssign for MouseButtons {
     #[inline]
     fn bitor_assign(&mut self, rhs: Self) {
@@ -137,6 +370,111 @@ impl std::ops::BitOrAssign<MouseButton> for MouseButtons {
     }
 }
 
+impl std::ops::BitAnd for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        MouseButtons(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitAnd<MouseButton> for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn bitand(self, rhs: MouseButton) -> Self::Output {
+        MouseButtons(self.0 & rhs.as_u32())
+    }
+}
+
+impl std::ops::BitXor for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        MouseButtons(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::BitXor<MouseButton> for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn bitxor(self, rhs: MouseButton) -> Self::Output {
+        MouseButtons(self.0 ^ rhs.as_u32())
+    }
+}
+
+impl std::ops::Sub for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        MouseButtons(self.0 & !rhs.0)
+    }
+}
+
+impl std::ops::Sub<MouseButton> for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn sub(self, rhs: MouseButton) -> Self::Output {
+        MouseButtons(self.0 & !rhs.as_u32())
+    }
+}
+
+impl std::ops::Not for MouseButtons {
+    type Output = MouseButtons;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        MouseButtons(!self.0)
+    }
+}
+
+impl std::ops::BitAndAssign for MouseButtons {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl std::ops::BitAndAssign<MouseButton> for MouseButtons {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: MouseButton) {
+        self.0 &= rhs.as_u32();
+    }
+}
+
+impl std::ops::BitXorAssign for MouseButtons {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl std::ops::BitXorAssign<MouseButton> for MouseButtons {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: MouseButton) {
+        self.0 ^= rhs.as_u32();
+    }
+}
+
+impl std::ops::SubAssign for MouseButtons {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl std::ops::SubAssign<MouseButton> for MouseButtons {
+    #[inline]
+    fn sub_assign(&mut self, rhs: MouseButton) {
+        self.0 &= !rhs.as_u32();
+    }
+}
+
 impl<const N: usize> From<[MouseButton; N]> for MouseButtons {
     #[inline]
     fn from(value: [MouseButton; N]) -> Self {
@@ -172,23 +510,63 @@ impl From<&Vec<MouseButton>> for MouseButtons {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for MouseButtons {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter())
+        } else {
+            serializer.serialize_u32(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MouseButtons {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let v = Vec::<MouseButton>::deserialize(deserializer)?;
+            Ok(v.into_iter().collect())
+        } else {
+            let bits = u32::deserialize(deserializer)?;
+            Ok(MouseButtons(bits))
+        }
+    }
+}
+
 #[cfg(windows)]
 impl From<WPARAM> for MouseButtons {
+    #[inline]
+    fn from(value: WPARAM) -> Self {
+        Self::from_mk_flags(value.0 as u32)
+    }
+}
+
+#[cfg(windows)]
+impl From<WPARAM> for Modifiers {
+    #[inline]
     fn from(value: WPARAM) -> Self {
-        let flags = MODIFIERKEYS_FLAGS(value.0 as u32);
-        let mut buttons = MouseButtons::new();
-        if flags.contains(MK_LBUTTON) {
-            buttons.0 |= MouseButton::Left.as_u32();
-        } else if flags.contains(MK_RBUTTON) {
-            buttons.0 |= MouseButton::Right.as_u32();
-        } else if flags.contains(MK_MBUTTON) {
-            buttons.0 |= MouseButton::Middle.as_u32();
-        } else if flags.contains(MK_XBUTTON1) {
-            buttons.0 |= MouseButton::Ex(0).as_u32();
-        } else if flags.contains(MK_XBUTTON2) {
-            buttons.0 |= MouseButton::Ex(1).as_u32();
+        Self::from_mk_flags(value.0 as u32)
+    }
+}
+
+impl Modifiers {
+    /// Decodes `MK_SHIFT` and `MK_CONTROL` from the key state flags of Windows mouse messages.
+    pub fn from_mk_flags(flags: u32) -> Self {
+        let mut modifiers = Self::new();
+        if flags & mk::SHIFT != 0 {
+            modifiers.insert(Modifier::Shift);
         }
-        buttons
+        if flags & mk::CONTROL != 0 {
+            modifiers.insert(Modifier::Ctrl);
+        }
+        modifiers
     }
 }
 
@@ -203,6 +581,23 @@ pub enum MouseWheelAxis {
 mod tests {
     use super::*;
 
+    #[test]
+    fn mouse_button_names() {
+        for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle] {
+            assert!(button.to_string().parse::<MouseButton>() == Ok(button));
+        }
+        for x in 0..MouseButton::EX_LEN {
+            let button = MouseButton::Ex(x);
+            assert!(button.to_string().parse::<MouseButton>() == Ok(button));
+        }
+        assert!(MouseButton::Ex(2).to_string() == "X3");
+        assert!("MIDDLE".parse::<MouseButton>() == Ok(MouseButton::Middle));
+        assert!("back".parse::<MouseButton>() == Ok(MouseButton::Ex(0)));
+        assert!("x0".parse::<MouseButton>().is_err());
+        assert!("X30".parse::<MouseButton>().is_err());
+        assert!("Foo".parse::<MouseButton>() == Err(ParseMouseButtonError("Foo".to_string())));
+    }
+
     #[test]
     fn mouse_buttons_contains() {
         let btns = MouseButtons(MouseButton::Left.as_u32());
@@ -224,6 +619,55 @@ mod tests {
         assert!(!btns.contains(MouseButton::Ex(0)));
     }
 
+    #[test]
+    fn mouse_buttons_from_mk_flags() {
+        let btns = MouseButtons::from_mk_flags(0x0001 | 0x0002 | 0x0040);
+        assert!(btns == MouseButton::Left | MouseButton::Right | MouseButton::Ex(1));
+        assert!(MouseButtons::from_mk_flags(0x0004 | 0x0008).is_empty());
+        let mods = Modifiers::from_mk_flags(0x0001 | 0x0004 | 0x0008);
+        assert!(mods == Modifier::Shift | Modifier::Ctrl);
+        assert!(Modifiers::from_mk_flags(0x0010).is_empty());
+    }
+
+    #[test]
+    fn mouse_buttons_set_ops() {
+        let a = MouseButton::Left | MouseButton::Right;
+        let b = MouseButton::Right | MouseButton::Ex(2);
+        assert!(a & b == MouseButtons::from([MouseButton::Right]));
+        assert!(a ^ b == MouseButton::Left | MouseButton::Ex(2));
+        assert!(a - b == MouseButtons::from([MouseButton::Left]));
+        assert!((!a).contains(MouseButton::Middle));
+        assert!(!(!a).contains(MouseButton::Left));
+        assert!((!a).contains(MouseButton::Ex(MouseButton::EX_LEN - 1)));
+        let mut c = a;
+        c.insert(MouseButton::Middle);
+        c.remove(MouseButton::Left);
+        assert!(c == MouseButton::Right | MouseButton::Middle);
+        assert!(c.len() == 2);
+        c -= MouseButton::Right;
+        assert!(c.len() == 1);
+        assert!(MouseButtons::new() < a);
+        assert!(a | b == a ^ b ^ (a & b));
+    }
+
+    #[test]
+    fn mouse_buttons_iter() {
+        let btns = MouseButton::Ex(3) | MouseButton::Left | MouseButton::Middle;
+        let mut iter = btns.iter();
+        assert!(iter.len() == 3);
+        assert!(iter.next() == Some(MouseButton::Left));
+        assert!(iter.next() == Some(MouseButton::Middle));
+        assert!(iter.next() == Some(MouseButton::Ex(3)));
+        assert!(iter.next().is_none());
+        let last = MouseButton::Ex(MouseButton::EX_LEN - 1);
+        assert!(MouseButtons::from([last]).iter().next() == Some(last));
+        let collected: MouseButtons = btns
+            .into_iter()
+            .filter(|b| *b != MouseButton::Middle)
+            .collect();
+        assert!(collected == MouseButton::Left | MouseButton::Ex(3));
+    }
+
     #[test]
     fn array_to_mouse_buttons() {
         let a = [MouseButton::Left, MouseButton::Right];
@@ -250,4 +694,28 @@ mod tests {
         assert!(!btns.contains(MouseButton::Middle));
         assert!(!btns.contains(MouseButton::Ex(0)));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn mouse_buttons_serde() {
+        let btns = MouseButton::Left | MouseButton::Ex(1);
+        let json = serde_json::to_string(&btns).unwrap();
+        assert!(json == r#"["Left","X2"]"#);
+        assert!(serde_json::from_str::<MouseButtons>(&json).unwrap() == btns);
+        assert!(serde_json::from_str::<MouseButton>(r#""back""#).unwrap() == MouseButton::Ex(0));
+        assert!(serde_json::from_str::<MouseButtons>(r#"["X30"]"#).is_err());
+        assert!(serde_json::from_str::<MouseButtons>(r#"[{"Ex":40}]"#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn mouse_button_serde_derived_form() {
+        let old = r#"["Left","Middle",{"Ex":1}]"#;
+        let btns = serde_json::from_str::<MouseButtons>(old).unwrap();
+        assert!(btns == MouseButton::Left | MouseButton::Middle | MouseButton::Ex(1));
+        let button = serde_json::from_str::<MouseButton>(r#"{"Ex":28}"#).unwrap();
+        assert!(button == MouseButton::Ex(28));
+        assert!(serde_json::from_str::<MouseButton>(r#"{"Ex":29}"#).is_err());
+        assert!(serde_json::from_str::<MouseButton>(r#"{"Foo":1}"#).is_err());
+    }
 }
@@ -0,0 +1,299 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ClickConfig {
+    pub double_click_time: Duration,
+    pub double_click_distance: i32,
+    pub drag_distance: i32,
+}
+
+impl Default for ClickConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            double_click_time: Duration::from_millis(500),
+            double_click_distance: 4,
+            drag_distance: 4,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseEvent {
+    /// Emitted when the button is pressed. `count` is 1 for a single click, 2 for a double click and so on.
+    Click {
+        button: MouseButton,
+        position: PhysicalPosition<i32>,
+        count: u32,
+    },
+    DragStart {
+        button: MouseButton,
+        start: PhysicalPosition<i32>,
+        position: PhysicalPosition<i32>,
+    },
+    DragMove {
+        button: MouseButton,
+        start: PhysicalPosition<i32>,
+        position: PhysicalPosition<i32>,
+    },
+    DragEnd {
+        button: MouseButton,
+        start: PhysicalPosition<i32>,
+        position: PhysicalPosition<i32>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Press {
+    button: MouseButton,
+    position: PhysicalPosition<i32>,
+    dragging: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LastClick {
+    button: MouseButton,
+    position: PhysicalPosition<i32>,
+    time: Instant,
+    count: u32,
+}
+
+/// Derives clicks and drags from button and move events.
+///
+/// Only the first button pressed while no other button is held can start a drag.
+#[derive(Clone, Debug)]
+pub struct MouseState {
+    config: ClickConfig,
+    buttons: MouseButtons,
+    position: PhysicalPosition<i32>,
+    press: Option<Press>,
+    last_click: Option<LastClick>,
+}
+
+impl MouseState {
+    #[inline]
+    pub fn new(config: ClickConfig) -> Self {
+        Self {
+            config,
+            buttons: MouseButtons::new(),
+            position: PhysicalPosition::new(0, 0),
+            press: None,
+            last_click: None,
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &ClickConfig {
+        &self.config
+    }
+
+    #[inline]
+    pub fn set_config(&mut self, config: ClickConfig) {
+        self.config = config;
+    }
+
+    #[inline]
+    pub fn buttons(&self) -> MouseButtons {
+        self.buttons
+    }
+
+    #[inline]
+    pub fn position(&self) -> PhysicalPosition<i32> {
+        self.position
+    }
+
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.press.is_some_and(|press| press.dragging)
+    }
+
+    pub fn input_button(
+        &mut self,
+        button: MouseButton,
+        state: ButtonState,
+        position: PhysicalPosition<i32>,
+        now: Instant,
+    ) -> Option<MouseEvent> {
+        self.position = position;
+        match state {
+            ButtonState::Pressed => {
+                if self.press.is_none() && self.buttons.is_empty() {
+                    self.press = Some(Press {
+                        button,
+                        position,
+                        dragging: false,
+                    });
+                }
+                self.buttons |= button;
+                let count = match self.last_click {
+                    Some(last)
+                        if last.button == button
+                            && now.saturating_duration_since(last.time)
+                                <= self.config.double_click_time
+                            && !exceeds(
+                                last.position,
+                                position,
+                                self.config.double_click_distance,
+                            ) =>
+                    {
+                        last.count + 1
+                    }
+                    _ => 1,
+                };
+                self.last_click = Some(LastClick {
+                    button,
+                    position,
+                    time: now,
+                    count,
+                });
+                Some(MouseEvent::Click {
+                    button,
+                    position,
+                    count,
+                })
+            }
+            ButtonState::Released => {
+                self.buttons.remove(button);
+                let press = self.press.filter(|press| press.button == button)?;
+                self.press = None;
+                press.dragging.then_some(MouseEvent::DragEnd {
+                    button,
+                    start: press.position,
+                    position,
+                })
+            }
+        }
+    }
+
+    pub fn input_move(&mut self, position: PhysicalPosition<i32>) -> Option<MouseEvent> {
+        self.position = position;
+        let press = self.press.as_mut()?;
+        if press.dragging {
+            return Some(MouseEvent::DragMove {
+                button: press.button,
+                start: press.position,
+                position,
+            });
+        }
+        if !exceeds(press.position, position, self.config.drag_distance) {
+            return None;
+        }
+        press.dragging = true;
+        self.last_click = None;
+        Some(MouseEvent::DragStart {
+            button: press.button,
+            start: press.position,
+            position,
+        })
+    }
+
+    /// Releases every button, e.g. on focus loss, and ends a drag in progress.
+    pub fn release_all(&mut self) -> Option<MouseEvent> {
+        self.buttons = MouseButtons::new();
+        self.last_click = None;
+        let press = self.press.take()?;
+        press.dragging.then_some(MouseEvent::DragEnd {
+            button: press.button,
+            start: press.position,
+            position: self.position,
+        })
+    }
+}
+
+impl Default for MouseState {
+    #[inline]
+    fn default() -> Self {
+        Self::new(ClickConfig::default())
+    }
+}
+
+#[inline]
+fn exceeds(a: PhysicalPosition<i32>, b: PhysicalPosition<i32>, distance: i32) -> bool {
+    (a.x - b.x).abs() > distance || (a.y - b.y).abs() > distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click_count(event: Option<MouseEvent>) -> u32 {
+        match event {
+            Some(MouseEvent::Click { count, .. }) => count,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn mouse_state_click() {
+        let mut state = MouseState::default();
+        let now = Instant::now();
+        let pos = PhysicalPosition::new(10, 10);
+        let left = MouseButton::Left;
+        assert!(click_count(state.input_button(left, ButtonState::Pressed, pos, now)) == 1);
+        assert!(state.buttons().contains(left));
+        assert!(
+            state
+                .input_button(left, ButtonState::Released, pos, now)
+                .is_none()
+        );
+        assert!(state.buttons().is_empty());
+        let now = now + Duration::from_millis(100);
+        let pos = PhysicalPosition::new(12, 9);
+        assert!(click_count(state.input_button(left, ButtonState::Pressed, pos, now)) == 2);
+        state.input_button(left, ButtonState::Released, pos, now);
+        assert!(click_count(state.input_button(left, ButtonState::Pressed, pos, now)) == 3);
+        state.input_button(left, ButtonState::Released, pos, now);
+        let right = MouseButton::Right;
+        assert!(click_count(state.input_button(right, ButtonState::Pressed, pos, now)) == 1);
+        state.input_button(right, ButtonState::Released, pos, now);
+        let now = now + Duration::from_secs(1);
+        assert!(click_count(state.input_button(right, ButtonState::Pressed, pos, now)) == 1);
+        state.input_button(right, ButtonState::Released, pos, now);
+        let far = PhysicalPosition::new(100, 100);
+        assert!(click_count(state.input_button(right, ButtonState::Pressed, far, now)) == 1);
+    }
+
+    #[test]
+    fn mouse_state_drag() {
+        let mut state = MouseState::default();
+        let now = Instant::now();
+        let start = PhysicalPosition::new(10, 10);
+        let left = MouseButton::Left;
+        state.input_button(left, ButtonState::Pressed, start, now);
+        assert!(state.input_move(PhysicalPosition::new(13, 10)).is_none());
+        let pos = PhysicalPosition::new(20, 10);
+        assert!(
+            state.input_move(pos)
+                == Some(MouseEvent::DragStart {
+                    button: left,
+                    start,
+                    position: pos
+                })
+        );
+        assert!(state.is_dragging());
+        assert!(matches!(
+            state.input_move(PhysicalPosition::new(30, 10)),
+            Some(MouseEvent::DragMove { .. })
+        ));
+        state.input_button(MouseButton::Right, ButtonState::Pressed, pos, now);
+        assert!(
+            state
+                .input_button(MouseButton::Right, ButtonState::Released, pos, now)
+                .is_none()
+        );
+        assert!(matches!(
+            state.input_button(left, ButtonState::Released, pos, now),
+            Some(MouseEvent::DragEnd { .. })
+        ));
+        assert!(!state.is_dragging());
+        assert!(state.input_move(PhysicalPosition::new(50, 50)).is_none());
+        state.input_button(left, ButtonState::Pressed, start, now);
+        state.input_move(pos);
+        assert!(matches!(
+            state.release_all(),
+            Some(MouseEvent::DragEnd { .. })
+        ));
+        assert!(state.buttons().is_empty());
+    }
+}
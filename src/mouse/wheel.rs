@@ -0,0 +1,160 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WheelUnit {
+    Notch,
+    Line,
+    Page,
+    Pixel,
+}
+
+impl std::fmt::Display for WheelUnit {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Converts between `WheelUnit`s. `line_height` and `page_size` are in logical pixels.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WheelScale {
+    pub lines_per_notch: f32,
+    pub line_height: f32,
+    pub page_size: f32,
+}
+
+impl WheelScale {
+    fn pixels(&self, unit: WheelUnit) -> f32 {
+        match unit {
+            WheelUnit::Notch => self.lines_per_notch * self.line_height,
+            WheelUnit::Line => self.line_height,
+            WheelUnit::Page => self.page_size,
+            WheelUnit::Pixel => 1.0,
+        }
+    }
+}
+
+impl Default for WheelScale {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            lines_per_notch: 3.0,
+            line_height: 16.0,
+            page_size: 480.0,
+        }
+    }
+}
+
+/// A wheel amount. Positive values scroll up on `Vertical` and right on `Horizontal`,
+/// the same as `WM_MOUSEWHEEL` and `WM_MOUSEHWHEEL`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WheelDelta {
+    pub axis: MouseWheelAxis,
+    pub value: f32,
+    pub unit: WheelUnit,
+}
+
+impl WheelDelta {
+    /// `WHEEL_DELTA`, the raw amount of one notch.
+    pub const NOTCH: i32 = 120;
+
+    #[inline]
+    pub fn new(axis: MouseWheelAxis, value: f32, unit: WheelUnit) -> Self {
+        Self { axis, value, unit }
+    }
+
+    #[inline]
+    pub fn from_raw(axis: MouseWheelAxis, raw: i32) -> Self {
+        Self::new(axis, raw as f32 / Self::NOTCH as f32, WheelUnit::Notch)
+    }
+
+    #[inline]
+    pub fn to_raw(&self, scale: &WheelScale) -> i32 {
+        (self.to_unit(WheelUnit::Notch, scale).value * Self::NOTCH as f32).round() as i32
+    }
+
+    pub fn to_unit(&self, unit: WheelUnit, scale: &WheelScale) -> Self {
+        if self.unit == unit {
+            return *self;
+        }
+        let value = self.value * scale.pixels(self.unit) / scale.pixels(unit);
+        Self::new(self.axis, value, unit)
+    }
+}
+
+/// Collects fractional wheel deltas, e.g. from precision touchpads, into whole notches.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct WheelAccumulator {
+    vertical: f32,
+    horizontal: f32,
+}
+
+impl WheelAccumulator {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the whole notches accumulated on `delta.axis` and keeps the remainder.
+    pub fn push(&mut self, delta: WheelDelta, scale: &WheelScale) -> i32 {
+        let acc = match delta.axis {
+            MouseWheelAxis::Vertical => &mut self.vertical,
+            MouseWheelAxis::Horizontal => &mut self.horizontal,
+        };
+        *acc += delta.to_unit(WheelUnit::Notch, scale).value;
+        let notches = acc.trunc();
+        *acc -= notches;
+        notches as i32
+    }
+
+    #[inline]
+    pub fn remainder(&self, axis: MouseWheelAxis) -> f32 {
+        match axis {
+            MouseWheelAxis::Vertical => self.vertical,
+            MouseWheelAxis::Horizontal => self.horizontal,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wheel_delta_to_unit() {
+        let scale = WheelScale::default();
+        let delta = WheelDelta::from_raw(MouseWheelAxis::Vertical, 240);
+        assert!(delta.value == 2.0);
+        assert!(delta.to_unit(WheelUnit::Line, &scale).value == 6.0);
+        assert!(delta.to_unit(WheelUnit::Pixel, &scale).value == 96.0);
+        assert!(delta.to_unit(WheelUnit::Page, &scale).value == 0.2);
+        let delta = WheelDelta::new(MouseWheelAxis::Vertical, -24.0, WheelUnit::Pixel);
+        assert!(delta.to_raw(&scale) == -60);
+    }
+
+    #[test]
+    fn wheel_accumulator() {
+        let scale = WheelScale::default();
+        let mut acc = WheelAccumulator::new();
+        let delta = WheelDelta::from_raw(MouseWheelAxis::Vertical, 30);
+        assert!(acc.push(delta, &scale) == 0);
+        assert!(acc.push(delta, &scale) == 0);
+        assert!(acc.push(delta, &scale) == 0);
+        assert!(acc.push(delta, &scale) == 1);
+        assert!(acc.remainder(MouseWheelAxis::Vertical) == 0.0);
+        let delta = WheelDelta::from_raw(MouseWheelAxis::Horizontal, -300);
+        assert!(acc.push(delta, &scale) == -2);
+        assert!(acc.remainder(MouseWheelAxis::Horizontal) == -0.5);
+        assert!(acc.remainder(MouseWheelAxis::Vertical) == 0.0);
+        acc.reset();
+        assert!(acc.remainder(MouseWheelAxis::Horizontal) == 0.0);
+    }
+}
@@ -0,0 +1,509 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PenButton {
+    /// The tip is touching the surface.
+    Tip,
+    Barrel,
+    /// The eraser end is used instead of the tip.
+    Eraser,
+}
+
+impl PenButton {
+    const ALL: [Self; 3] = [Self::Tip, Self::Barrel, Self::Eraser];
+
+    #[inline]
+    fn as_u8(&self) -> u8 {
+        match self {
+            Self::Tip => 0x01,
+            Self::Barrel => 0x01 << 1,
+            Self::Eraser => 0x01 << 2,
+        }
+    }
+}
+
+impl std::fmt::Display for PenButton {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PenButtons(u8);
+
+impl PenButtons {
+    const ALL: u8 = 0x07;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub fn contains(&self, button: PenButton) -> bool {
+        self.0 & button.as_u8() != 0
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[inline]
+    pub fn insert(&mut self, button: PenButton) {
+        self.0 |= button.as_u8();
+    }
+
+    #[inline]
+    pub fn remove(&mut self, button: PenButton) {
+        self.0 &= !button.as_u8();
+    }
+
+    #[inline]
+    pub fn iter(&self) -> PenButtonsIter {
+        PenButtonsIter(self.0)
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<PenButton> {
+        self.iter().collect()
+    }
+}
+
+impl std::fmt::Debug for PenButtons {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.to_vec())
+    }
+}
+
+impl std::fmt::Display for PenButtons {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub struct PenButtonsIter(u8);
+
+impl Iterator for PenButtonsIter {
+    type Item = PenButton;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(PenButton::ALL[index as usize])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PenButtonsIter {}
+
+impl IntoIterator for PenButtons {
+    type Item = PenButton;
+    type IntoIter = PenButtonsIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &PenButtons {
+    type Item = PenButton;
+    type IntoIter = PenButtonsIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<PenButton> for PenButtons {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = PenButton>>(iter: I) -> Self {
+        iter.into_iter().fold(PenButtons::new(), |r, b| r | b)
+    }
+}
+
+impl From<PenButton> for PenButtons {
+    #[inline]
+    fn from(value: PenButton) -> Self {
+        Self(value.as_u8())
+    }
+}
+
+impl<const N: usize> From<[PenButton; N]> for PenButtons {
+    #[inline]
+    fn from(value: [PenButton; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl std::ops::BitOr for PenButton {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        PenButtons(self.as_u8() | rhs.as_u8())
+    }
+}
+
+impl std::ops::BitOr<PenButton> for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitor(self, rhs: PenButton) -> Self::Output {
+        PenButtons(self.0 | rhs.as_u8())
+    }
+}
+
+impl std::ops::BitOr for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        PenButtons(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign<PenButton> for PenButtons {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: PenButton) {
+        self.0 |= rhs.as_u8();
+    }
+}
+
+impl std::ops::BitOrAssign for PenButtons {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl std::ops::BitAnd for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        PenButtons(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitAnd<PenButton> for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitand(self, rhs: PenButton) -> Self::Output {
+        PenButtons(self.0 & rhs.as_u8())
+    }
+}
+
+impl std::ops::BitXor for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        PenButtons(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::BitXor<PenButton> for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn bitxor(self, rhs: PenButton) -> Self::Output {
+        PenButtons(self.0 ^ rhs.as_u8())
+    }
+}
+
+impl std::ops::Sub for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        PenButtons(self.0 & !rhs.0)
+    }
+}
+
+impl std::ops::Sub<PenButton> for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn sub(self, rhs: PenButton) -> Self::Output {
+        PenButtons(self.0 & !rhs.as_u8())
+    }
+}
+
+impl std::ops::Not for PenButtons {
+    type Output = PenButtons;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        PenButtons(!self.0 & Self::ALL)
+    }
+}
+
+impl std::ops::BitAndAssign for PenButtons {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl std::ops::BitAndAssign<PenButton> for PenButtons {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: PenButton) {
+        self.0 &= rhs.as_u8();
+    }
+}
+
+impl std::ops::BitXorAssign for PenButtons {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl std::ops::BitXorAssign<PenButton> for PenButtons {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: PenButton) {
+        self.0 ^= rhs.as_u8();
+    }
+}
+
+impl std::ops::SubAssign for PenButtons {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl std::ops::SubAssign<PenButton> for PenButtons {
+    #[inline]
+    fn sub_assign(&mut self, rhs: PenButton) {
+        self.0 &= !rhs.as_u8();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PenButtons {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter())
+        } else {
+            serializer.serialize_u8(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PenButtons {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let v = Vec::<PenButton>::deserialize(deserializer)?;
+            Ok(v.into_iter().collect())
+        } else {
+            let bits = u8::deserialize(deserializer)?;
+            Ok(PenButtons(bits & Self::ALL))
+        }
+    }
+}
+
+/// Tilt of the pen in degrees from the perpendicular, in `-90.0..=90.0`.
+///
+/// Positive `x` leans toward the right and positive `y` toward the user.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PenTilt {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl PenTilt {
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// The angle between the pen and the surface in radians, `PI / 2` when upright.
+    pub fn altitude(&self) -> f32 {
+        let (tx, ty) = (self.x.to_radians().tan(), self.y.to_radians().tan());
+        1.0f32.atan2(tx.hypot(ty))
+    }
+
+    /// The direction the pen leans toward in radians, clockwise from the positive x axis.
+    pub fn azimuth(&self) -> f32 {
+        let (tx, ty) = (self.x.to_radians().tan(), self.y.to_radians().tan());
+        ty.atan2(tx).rem_euclid(std::f32::consts::TAU)
+    }
+}
+
+/// A sample of pen input.
+///
+/// `pressure` is in `0.0..=1.0`, `twist` is the clockwise rotation around the pen's axis in
+/// degrees `0.0..360.0`, and `hover_distance` is in `0.0..=1.0` of the detection range, or
+/// `None` when the device does not report it.
+///
+/// `PenButton::Tip` in `buttons` decides whether the pen is touching, and `hover_distance` is
+/// ignored while it is. The logical position is derived from `position` and `dpi`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PenSample {
+    pub id: PointerId,
+    pub position: PhysicalPosition<f64>,
+    pub dpi: f64,
+    pub pressure: f32,
+    pub tilt: PenTilt,
+    pub twist: f32,
+    pub buttons: PenButtons,
+    pub hover_distance: Option<f32>,
+}
+
+impl PenSample {
+    #[inline]
+    pub fn new(id: PointerId, position: impl Into<PhysicalPosition<f64>>, dpi: f64) -> Self {
+        Self {
+            id,
+            position: position.into(),
+            dpi,
+            pressure: 0.0,
+            tilt: PenTilt::default(),
+            twist: 0.0,
+            buttons: PenButtons::new(),
+            hover_distance: None,
+        }
+    }
+
+    #[inline]
+    pub fn logical_position(&self) -> LogicalPosition<f64> {
+        self.position.to_logical(self.dpi)
+    }
+
+    #[inline]
+    pub fn is_touching(&self) -> bool {
+        self.buttons.contains(PenButton::Tip)
+    }
+
+    /// `hover_distance` while the pen is not touching.
+    #[inline]
+    pub fn hover_distance(&self) -> Option<f32> {
+        if self.is_touching() {
+            None
+        } else {
+            self.hover_distance
+        }
+    }
+
+    #[inline]
+    pub fn is_eraser(&self) -> bool {
+        self.buttons.contains(PenButton::Eraser)
+    }
+
+    #[inline]
+    pub fn to_touch_point(&self) -> TouchPoint {
+        TouchPoint {
+            pressure: Some(self.pressure),
+            ..TouchPoint::new(self.id, PointerKind::Pen, self.position)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pen_buttons() {
+        let mut buttons = PenButton::Tip | PenButton::Eraser;
+        assert!(buttons.len() == 2);
+        assert!(buttons.contains(PenButton::Eraser));
+        assert!(!buttons.contains(PenButton::Barrel));
+        buttons.remove(PenButton::Tip);
+        buttons |= PenButton::Barrel;
+        assert!(buttons.to_vec() == [PenButton::Barrel, PenButton::Eraser]);
+        assert!(buttons == PenButtons::from([PenButton::Eraser, PenButton::Barrel]));
+        assert!(PenButtons::default().is_empty());
+    }
+
+    #[test]
+    fn pen_buttons_set_ops() {
+        let a = PenButton::Tip | PenButton::Barrel;
+        let b = PenButton::Barrel | PenButton::Eraser;
+        assert!(a & b == PenButtons::from(PenButton::Barrel));
+        assert!(a ^ b == PenButton::Tip | PenButton::Eraser);
+        assert!(a - b == PenButtons::from(PenButton::Tip));
+        assert!(!a == PenButtons::from(PenButton::Eraser));
+        assert!(!PenButtons::new() == PenButton::Tip | PenButton::Barrel | PenButton::Eraser);
+        let mut c = a;
+        c -= PenButton::Tip;
+        c ^= PenButton::Eraser;
+        assert!(c == b);
+        c &= PenButton::Eraser;
+        assert!(c.into_iter().collect::<Vec<_>>() == [PenButton::Eraser]);
+        assert!((&a).into_iter().len() == 2);
+    }
+
+    #[test]
+    fn pen_sample() {
+        let mut sample = PenSample::new(PointerId(1), (192.0, 96.0), 192.0);
+        assert!(sample.logical_position() == LogicalPosition::new(96.0, 48.0));
+        sample.position = PhysicalPosition::new(384.0, 96.0);
+        assert!(sample.logical_position() == LogicalPosition::new(192.0, 48.0));
+        sample.pressure = 0.5;
+        sample.hover_distance = Some(0.1);
+        assert!(sample.hover_distance() == Some(0.1));
+        sample.buttons = PenButton::Tip.into();
+        assert!(sample.is_touching());
+        assert!(sample.hover_distance().is_none());
+        assert!(!sample.is_eraser());
+        let point = sample.to_touch_point();
+        assert!(point.kind == PointerKind::Pen);
+        assert!(point.pressure == Some(0.5));
+    }
+
+    #[test]
+    fn pen_tilt() {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+        let upright = PenTilt::default();
+        assert!((upright.altitude() - FRAC_PI_2).abs() < 1e-6);
+        let tilt = PenTilt::new(45.0, 0.0);
+        assert!((tilt.altitude() - FRAC_PI_4).abs() < 1e-6);
+        assert!(tilt.azimuth().abs() < 1e-6);
+        let tilt = PenTilt::new(0.0, 45.0);
+        assert!((tilt.azimuth() - FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn pen_sample_serde() {
+        let mut sample = PenSample::new(PointerId(2), (10.0, 20.0), 96.0);
+        sample.buttons = PenButton::Tip | PenButton::Barrel;
+        sample.hover_distance = Some(0.25);
+        let json = serde_json::to_string(&sample).unwrap();
+        assert!(json.contains(r#""buttons":["Tip","Barrel"]"#));
+        assert!(serde_json::from_str::<PenSample>(&json).unwrap() == sample);
+    }
+}
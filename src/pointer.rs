@@ -0,0 +1,59 @@
+use super::*;
+
+mod gesture;
+
+pub use gesture::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointerId(pub u64);
+
+impl std::fmt::Display for PointerId {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointerKind {
+    Mouse,
+    Pen,
+    Touch,
+}
+
+impl std::fmt::Display for PointerKind {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A contact of a pointer. `pressure` is in `0.0..=1.0` and `None` when the device does not report it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TouchPoint {
+    pub id: PointerId,
+    pub kind: PointerKind,
+    pub position: PhysicalPosition<f64>,
+    pub pressure: Option<f32>,
+    pub contact_size: Option<PhysicalSize<f64>>,
+}
+
+impl TouchPoint {
+    #[inline]
+    pub fn new(
+        id: PointerId,
+        kind: PointerKind,
+        position: impl Into<PhysicalPosition<f64>>,
+    ) -> Self {
+        Self {
+            id,
+            kind,
+            position: position.into(),
+            pressure: None,
+            contact_size: None,
+        }
+    }
+}
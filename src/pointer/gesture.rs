@@ -0,0 +1,505 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+/// Distances are in physical pixels and angles in radians.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GestureConfig {
+    pub tap_distance: f64,
+    pub tap_time: Duration,
+    pub long_press_time: Duration,
+    pub pinch_threshold: f64,
+    pub rotate_threshold: f64,
+}
+
+impl Default for GestureConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            tap_distance: 10.0,
+            tap_time: Duration::from_millis(300),
+            long_press_time: Duration::from_millis(500),
+            pinch_threshold: 0.05,
+            rotate_threshold: 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+    Cancelled,
+}
+
+impl std::fmt::Display for GesturePhase {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gesture {
+    Tap {
+        position: PhysicalPosition<f64>,
+    },
+    LongPress {
+        position: PhysicalPosition<f64>,
+    },
+    /// `translation` is from where the contact started.
+    Pan {
+        phase: GesturePhase,
+        position: PhysicalPosition<f64>,
+        translation: PhysicalPosition<f64>,
+    },
+    /// `scale` is the distance between the two contacts relative to when they both touched.
+    Pinch {
+        phase: GesturePhase,
+        center: PhysicalPosition<f64>,
+        scale: f64,
+    },
+    /// `rotation` is clockwise in radians from when both contacts touched.
+    Rotate {
+        phase: GesturePhase,
+        center: PhysicalPosition<f64>,
+        rotation: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Contact {
+    id: PointerId,
+    start: PhysicalPosition<f64>,
+    position: PhysicalPosition<f64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Mode {
+    Idle,
+    Possible {
+        since: Instant,
+    },
+    LongPressed,
+    Panning,
+    Multi {
+        distance: f64,
+        angle: f64,
+        pinching: bool,
+        rotating: bool,
+    },
+    /// Waits for every contact to lift after a gesture that cannot continue.
+    Done,
+}
+
+/// Turns touch contacts into tap, long-press, pan, pinch and rotate gestures.
+///
+/// One contact makes a tap, a long press or a pan. Two contacts make a pinch and a rotation
+/// at the same time; further contacts are ignored.
+///
+/// A still contact released within `tap_time` is a tap, and one held for `long_press_time` is
+/// a long press; releasing it in between makes no gesture. Call `update` regularly while a
+/// contact is held to report long presses before release, otherwise they are reported on
+/// release.
+#[derive(Clone, Debug)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    contacts: Vec<Contact>,
+    mode: Mode,
+}
+
+impl GestureRecognizer {
+    #[inline]
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            contacts: vec![],
+            mode: Mode::Idle,
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    #[inline]
+    pub fn contacts(&self) -> usize {
+        self.contacts.len()
+    }
+
+    pub fn input(&mut self, point: &TouchPoint, phase: TouchPhase, now: Instant) -> Vec<Gesture> {
+        let mut gestures = vec![];
+        match phase {
+            TouchPhase::Started => self.start(point, &mut gestures, now),
+            TouchPhase::Moved => self.moved(point, &mut gestures),
+            TouchPhase::Ended => self.end(point, GesturePhase::Ended, &mut gestures, now),
+            TouchPhase::Cancelled => self.end(point, GesturePhase::Cancelled, &mut gestures, now),
+        }
+        gestures
+    }
+
+    /// Reports a long press once a single contact has been held still long enough.
+    pub fn update(&mut self, now: Instant) -> Option<Gesture> {
+        let Mode::Possible { since } = self.mode else {
+            return None;
+        };
+        if now.saturating_duration_since(since) < self.config.long_press_time {
+            return None;
+        }
+        self.mode = Mode::LongPressed;
+        Some(Gesture::LongPress {
+            position: self.contacts[0].position,
+        })
+    }
+
+    /// Drops every contact, cancelling gestures in progress.
+    pub fn cancel(&mut self) -> Vec<Gesture> {
+        let mut gestures = vec![];
+        self.finish(GesturePhase::Cancelled, &mut gestures);
+        self.contacts.clear();
+        self.mode = Mode::Idle;
+        gestures
+    }
+
+    fn start(&mut self, point: &TouchPoint, gestures: &mut Vec<Gesture>, now: Instant) {
+        if self.contacts.iter().any(|c| c.id == point.id) {
+            return;
+        }
+        self.contacts.push(Contact {
+            id: point.id,
+            start: point.position,
+            position: point.position,
+        });
+        match self.contacts.len() {
+            1 => self.mode = Mode::Possible { since: now },
+            2 if self.mode != Mode::Done => {
+                self.finish(GesturePhase::Ended, gestures);
+                let (a, b) = (self.contacts[0].position, self.contacts[1].position);
+                self.mode = Mode::Multi {
+                    distance: distance(a, b),
+                    angle: angle(a, b),
+                    pinching: false,
+                    rotating: false,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn moved(&mut self, point: &TouchPoint, gestures: &mut Vec<Gesture>) {
+        let Some(index) = self.contacts.iter().position(|c| c.id == point.id) else {
+            return;
+        };
+        self.contacts[index].position = point.position;
+        let contact = self.contacts[index];
+        match &mut self.mode {
+            Mode::Possible { .. } | Mode::LongPressed => {
+                if distance(contact.start, contact.position) > self.config.tap_distance {
+                    self.mode = Mode::Panning;
+                    gestures.push(pan(GesturePhase::Began, &contact));
+                }
+            }
+            Mode::Panning => gestures.push(pan(GesturePhase::Changed, &contact)),
+            Mode::Multi {
+                distance: start_distance,
+                angle: start_angle,
+                pinching,
+                rotating,
+            } => {
+                if index > 1 {
+                    return;
+                }
+                let (a, b) = (self.contacts[0].position, self.contacts[1].position);
+                let center = midpoint(a, b);
+                let scale = if *start_distance > 0.0 {
+                    distance(a, b) / *start_distance
+                } else {
+                    1.0
+                };
+                let rotation = normalize_angle(angle(a, b) - *start_angle);
+                if *pinching || (scale - 1.0).abs() > self.config.pinch_threshold {
+                    let phase = if *pinching {
+                        GesturePhase::Changed
+                    } else {
+                        GesturePhase::Began
+                    };
+                    *pinching = true;
+                    gestures.push(Gesture::Pinch {
+                        phase,
+                        center,
+                        scale,
+                    });
+                }
+                if *rotating || rotation.abs() > self.config.rotate_threshold {
+                    let phase = if *rotating {
+                        GesturePhase::Changed
+                    } else {
+                        GesturePhase::Began
+                    };
+                    *rotating = true;
+                    gestures.push(Gesture::Rotate {
+                        phase,
+                        center,
+                        rotation,
+                    });
+                }
+            }
+            Mode::Idle | Mode::Done => {}
+        }
+    }
+
+    fn end(
+        &mut self,
+        point: &TouchPoint,
+        phase: GesturePhase,
+        gestures: &mut Vec<Gesture>,
+        now: Instant,
+    ) {
+        let Some(index) = self.contacts.iter().position(|c| c.id == point.id) else {
+            return;
+        };
+        self.contacts[index].position = point.position;
+        if let Mode::Possible { since } = self.mode
+            && phase == GesturePhase::Ended
+        {
+            let held = now.saturating_duration_since(since);
+            let position = point.position;
+            if held <= self.config.tap_time {
+                gestures.push(Gesture::Tap { position });
+            } else if held >= self.config.long_press_time {
+                gestures.push(Gesture::LongPress { position });
+            }
+        }
+        if index < 2 || phase == GesturePhase::Cancelled {
+            self.finish(phase, gestures);
+            self.mode = Mode::Done;
+        }
+        self.contacts.remove(index);
+        if self.contacts.is_empty() {
+            self.mode = Mode::Idle;
+        }
+    }
+
+    /// Ends the gestures in progress with `phase`.
+    fn finish(&mut self, phase: GesturePhase, gestures: &mut Vec<Gesture>) {
+        match self.mode {
+            Mode::Panning => gestures.push(pan(phase, &self.contacts[0])),
+            Mode::Multi {
+                distance: start_distance,
+                angle: start_angle,
+                pinching,
+                rotating,
+            } => {
+                let (a, b) = (self.contacts[0].position, self.contacts[1].position);
+                let center = midpoint(a, b);
+                if pinching {
+                    let scale = if start_distance > 0.0 {
+                        distance(a, b) / start_distance
+                    } else {
+                        1.0
+                    };
+                    gestures.push(Gesture::Pinch {
+                        phase,
+                        center,
+                        scale,
+                    });
+                }
+                if rotating {
+                    gestures.push(Gesture::Rotate {
+                        phase,
+                        center,
+                        rotation: normalize_angle(angle(a, b) - start_angle),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for GestureRecognizer {
+    #[inline]
+    fn default() -> Self {
+        Self::new(GestureConfig::default())
+    }
+}
+
+#[inline]
+fn pan(phase: GesturePhase, contact: &Contact) -> Gesture {
+    Gesture::Pan {
+        phase,
+        position: contact.position,
+        translation: PhysicalPosition::new(
+            contact.position.x - contact.start.x,
+            contact.position.y - contact.start.y,
+        ),
+    }
+}
+
+#[inline]
+fn distance(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+#[inline]
+fn angle(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> f64 {
+    (b.y - a.y).atan2(b.x - a.x)
+}
+
+#[inline]
+fn midpoint(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> PhysicalPosition<f64> {
+    PhysicalPosition::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+}
+
+fn normalize_angle(a: f64) -> f64 {
+    use std::f64::consts::PI;
+    let a = a.rem_euclid(2.0 * PI);
+    if a > PI { a - 2.0 * PI } else { a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, x: f64, y: f64) -> TouchPoint {
+        TouchPoint::new(PointerId(id), PointerKind::Touch, (x, y))
+    }
+
+    #[test]
+    fn tap_and_long_press() {
+        let mut r = GestureRecognizer::default();
+        let now = Instant::now();
+        assert!(
+            r.input(&touch(0, 10.0, 10.0), TouchPhase::Started, now)
+                .is_empty()
+        );
+        assert!(
+            r.input(&touch(0, 13.0, 10.0), TouchPhase::Moved, now)
+                .is_empty()
+        );
+        let later = now + Duration::from_millis(100);
+        let g = r.input(&touch(0, 13.0, 10.0), TouchPhase::Ended, later);
+        assert!(
+            g == [Gesture::Tap {
+                position: PhysicalPosition::new(13.0, 10.0)
+            }]
+        );
+        assert!(r.contacts() == 0);
+        r.input(&touch(1, 10.0, 10.0), TouchPhase::Started, now);
+        assert!(r.update(now + Duration::from_millis(100)).is_none());
+        assert!(matches!(
+            r.update(now + Duration::from_millis(600)),
+            Some(Gesture::LongPress { .. })
+        ));
+        assert!(r.update(now + Duration::from_millis(700)).is_none());
+        let g = r.input(
+            &touch(1, 10.0, 10.0),
+            TouchPhase::Ended,
+            now + Duration::from_secs(1),
+        );
+        assert!(g.is_empty());
+    }
+
+    #[test]
+    fn pan() {
+        let mut r = GestureRecognizer::default();
+        let now = Instant::now();
+        r.input(&touch(0, 0.0, 0.0), TouchPhase::Started, now);
+        let g = r.input(&touch(0, 20.0, 0.0), TouchPhase::Moved, now);
+        assert!(matches!(
+            g[..],
+            [Gesture::Pan {
+                phase: GesturePhase::Began,
+                ..
+            }]
+        ));
+        let g = r.input(&touch(0, 30.0, 5.0), TouchPhase::Moved, now);
+        assert!(
+            g == [Gesture::Pan {
+                phase: GesturePhase::Changed,
+                position: PhysicalPosition::new(30.0, 5.0),
+                translation: PhysicalPosition::new(30.0, 5.0),
+            }]
+        );
+        let g = r.input(&touch(0, 30.0, 5.0), TouchPhase::Ended, now);
+        assert!(matches!(
+            g[..],
+            [Gesture::Pan {
+                phase: GesturePhase::Ended,
+                ..
+            }]
+        ));
+        assert!(r.update(now + Duration::from_secs(1)).is_none());
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut r = GestureRecognizer::default();
+        let now = Instant::now();
+        r.input(&touch(0, 0.0, 0.0), TouchPhase::Started, now);
+        r.input(&touch(1, 100.0, 0.0), TouchPhase::Started, now);
+        assert!(
+            r.input(&touch(1, 102.0, 0.0), TouchPhase::Moved, now)
+                .is_empty()
+        );
+        let g = r.input(&touch(1, 200.0, 0.0), TouchPhase::Moved, now);
+        assert!(
+            g == [Gesture::Pinch {
+                phase: GesturePhase::Began,
+                center: PhysicalPosition::new(100.0, 0.0),
+                scale: 2.0,
+            }]
+        );
+        let g = r.input(&touch(1, 0.0, 200.0), TouchPhase::Moved, now);
+        assert!(g.len() == 2);
+        let Gesture::Rotate {
+            phase, rotation, ..
+        } = g[1]
+        else {
+            panic!();
+        };
+        assert!(phase == GesturePhase::Began);
+        assert!((rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        let g = r.input(&touch(0, 0.0, 0.0), TouchPhase::Ended, now);
+        assert!(g.len() == 2);
+        assert!(g.iter().all(|g| matches!(
+            g,
+            Gesture::Pinch {
+                phase: GesturePhase::Ended,
+                ..
+            } | Gesture::Rotate {
+                phase: GesturePhase::Ended,
+                ..
+            }
+        )));
+        assert!(
+            r.input(&touch(1, 50.0, 50.0), TouchPhase::Moved, now)
+                .is_empty()
+        );
+        assert!(
+            r.input(&touch(1, 50.0, 50.0), TouchPhase::Ended, now)
+                .is_empty()
+        );
+        assert!(r.contacts() == 0);
+    }
+
+    #[test]
+    fn cancel_gesture() {
+        let mut r = GestureRecognizer::default();
+        let now = Instant::now();
+        r.input(&touch(0, 0.0, 0.0), TouchPhase::Started, now);
+        r.input(&touch(0, 50.0, 0.0), TouchPhase::Moved, now);
+        let g = r.cancel();
+        assert!(matches!(
+            g[..],
+            [Gesture::Pan {
+                phase: GesturePhase::Cancelled,
+                ..
+            }]
+        ));
+        assert!(r.contacts() == 0);
+    }
+}
@@ -0,0 +1,1010 @@
+use super::*;
+use std::io::{BufRead, Read, Write};
+use std::time::{Duration, Instant};
+
+/// The version written to and accepted from recordings.
+pub const RECORDING_VERSION: u32 = 1;
+
+const FORMAT_NAME: &str = "gedv-recording";
+const BINARY_MAGIC: &[u8; 4] = b"GEDV";
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    InvalidHeader,
+    UnsupportedVersion(u32),
+    InvalidData(&'static str),
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Json(e) => write!(f, "{}", e),
+            Self::InvalidHeader => write!(f, "not a gedv recording"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported recording version: {}", v),
+            Self::InvalidData(s) => write!(f, "invalid recording data: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RecordingError {
+    #[inline]
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for RecordingError {
+    #[inline]
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// An event with its time from the start of the recording, in microsecond resolution.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecordedEvent {
+    #[serde(rename = "time_us", with = "micros")]
+    pub time: Duration,
+    pub event: InputEvent,
+}
+
+mod micros {
+    use serde::Deserialize;
+    use std::time::Duration;
+
+    pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(value.as_micros() as u64)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_micros)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+/// A recorded session.
+///
+/// The JSON lines form starts with a `{"format":"gedv-recording","version":1}` line followed
+/// by one `RecordedEvent` per line. The binary form starts with `GEDV` and the version as
+/// little-endian `u32`, followed by the events.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Recording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write_json_lines(&self, mut writer: impl Write) -> Result<(), RecordingError> {
+        let header = Header {
+            format: FORMAT_NAME.to_string(),
+            version: RECORDING_VERSION,
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writeln!(writer)?;
+        for event in &self.events {
+            serde_json::to_writer(&mut writer, event)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_json_lines(reader: impl BufRead) -> Result<Self, RecordingError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(RecordingError::InvalidHeader)??;
+        let header: Header =
+            serde_json::from_str(&header).map_err(|_| RecordingError::InvalidHeader)?;
+        if header.format != FORMAT_NAME {
+            return Err(RecordingError::InvalidHeader);
+        }
+        if header.version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(header.version));
+        }
+        let mut events = vec![];
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            events.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self { events })
+    }
+
+    pub fn write_binary(&self, mut writer: impl Write) -> Result<(), RecordingError> {
+        let mut buf = BINARY_MAGIC.to_vec();
+        buf.extend_from_slice(&RECORDING_VERSION.to_le_bytes());
+        for event in &self.events {
+            put_u64(&mut buf, event.time.as_micros() as u64);
+            put_event(&mut buf, &event.event);
+        }
+        writer.write_all(&buf)?;
+        Ok(())
+    }
+
+    pub fn read_binary(mut reader: impl Read) -> Result<Self, RecordingError> {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf)?;
+        let mut src = Source(&buf);
+        if src.take(4).ok() != Some(BINARY_MAGIC.as_slice()) {
+            return Err(RecordingError::InvalidHeader);
+        }
+        let version = src.u32().map_err(|_| RecordingError::InvalidHeader)?;
+        if version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+        let mut events = vec![];
+        while !src.0.is_empty() {
+            let time = Duration::from_micros(src.u64()?);
+            let event = src.event()?;
+            events.push(RecordedEvent { time, event });
+        }
+        Ok(Self { events })
+    }
+}
+
+/// Collects events with their time since the recorder was created.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    start: Instant,
+    recording: Recording,
+}
+
+impl Recorder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_start(Instant::now())
+    }
+
+    #[inline]
+    pub fn with_start(start: Instant) -> Self {
+        Self {
+            start,
+            recording: Recording::new(),
+        }
+    }
+
+    #[inline]
+    pub fn record(&mut self, event: InputEvent) {
+        self.record_at(Instant::now(), event);
+    }
+
+    #[inline]
+    pub fn record_at(&mut self, now: Instant, event: InputEvent) {
+        let time = now.saturating_duration_since(self.start);
+        self.recording.events.push(RecordedEvent { time, event });
+    }
+
+    #[inline]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    #[inline]
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+impl Default for Recorder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replays a `Recording`.
+///
+/// `speed` scales the timing: `1.0` is the original timing, `2.0` twice as fast, and
+/// `f64::INFINITY` yields every event immediately. Playback starts at the first `poll` or
+/// `next`.
+#[derive(Clone, Debug)]
+pub struct Player {
+    events: std::vec::IntoIter<RecordedEvent>,
+    speed: f64,
+    start: Option<Instant>,
+}
+
+impl Player {
+    /// Panics if `speed` is not positive.
+    #[inline]
+    pub fn new(recording: Recording, speed: f64) -> Self {
+        assert!(speed > 0.0, "speed must be positive");
+        Self {
+            events: recording.events.into_iter(),
+            speed,
+            start: None,
+        }
+    }
+
+    /// Returns the next event if it is due at `now`.
+    pub fn poll(&mut self, now: Instant) -> Option<InputEvent> {
+        let start = *self.start.get_or_insert(now);
+        let due = start + self.scale(self.peek()?.time);
+        if due > now {
+            return None;
+        }
+        self.events.next().map(|e| e.event)
+    }
+
+    /// Returns `None` when every event has been played.
+    pub fn time_until_next(&self, now: Instant) -> Option<Duration> {
+        let offset = self.scale(self.peek()?.time);
+        Some(match self.start {
+            Some(start) => (start + offset).saturating_duration_since(now),
+            None => offset,
+        })
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.peek().is_none()
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&RecordedEvent> {
+        self.events.as_slice().first()
+    }
+
+    fn scale(&self, time: Duration) -> Duration {
+        if self.speed.is_infinite() {
+            Duration::ZERO
+        } else {
+            time.div_f64(self.speed)
+        }
+    }
+}
+
+/// Blocks until each event is due.
+impl Iterator for Player {
+    type Item = InputEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = Instant::now();
+        let wait = self.time_until_next(now)?;
+        self.start.get_or_insert(now);
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+        self.events.next().map(|e| e.event)
+    }
+}
+
+mod tag {
+    pub const KEY: u8 = 0;
+    pub const TEXT: u8 = 1;
+    pub const MOUSE_BUTTON: u8 = 2;
+    pub const MOUSE_MOVE: u8 = 3;
+    pub const MOUSE_WHEEL: u8 = 4;
+    pub const CURSOR_ENTERED: u8 = 5;
+    pub const CURSOR_LEFT: u8 = 6;
+    pub const TOUCH: u8 = 7;
+    pub const FOCUSED: u8 = 8;
+    pub const UNFOCUSED: u8 = 9;
+}
+
+/// A fixed number for each virtual key. `0` is `Other`, whose value follows the tag.
+///
+/// The numbers are part of the binary format and must not change.
+fn vkey_tag(vkey: VirtualKey) -> u32 {
+    match vkey {
+        VirtualKey::Other(_) => 0,
+        VirtualKey::Esc => 1,
+        VirtualKey::Tab => 2,
+        VirtualKey::CapsLock => 3,
+        VirtualKey::Shift => 4,
+        VirtualKey::Ctrl => 5,
+        VirtualKey::Alt => 6,
+        VirtualKey::LShift => 7,
+        VirtualKey::RShift => 8,
+        VirtualKey::LCtrl => 9,
+        VirtualKey::RCtrl => 10,
+        VirtualKey::LAlt => 11,
+        VirtualKey::RAlt => 12,
+        VirtualKey::LMeta => 13,
+        VirtualKey::RMeta => 14,
+        VirtualKey::ContextMenu => 15,
+        VirtualKey::BackSpace => 16,
+        VirtualKey::Enter => 17,
+        VirtualKey::Space => 18,
+        VirtualKey::PrintScreen => 19,
+        VirtualKey::ScrollLock => 20,
+        VirtualKey::Pause => 21,
+        VirtualKey::Insert => 22,
+        VirtualKey::Delete => 23,
+        VirtualKey::Home => 24,
+        VirtualKey::End => 25,
+        VirtualKey::PageUp => 26,
+        VirtualKey::PageDown => 27,
+        VirtualKey::Up => 28,
+        VirtualKey::Down => 29,
+        VirtualKey::Left => 30,
+        VirtualKey::Right => 31,
+        VirtualKey::Key1 => 32,
+        VirtualKey::Key2 => 33,
+        VirtualKey::Key3 => 34,
+        VirtualKey::Key4 => 35,
+        VirtualKey::Key5 => 36,
+        VirtualKey::Key6 => 37,
+        VirtualKey::Key7 => 38,
+        VirtualKey::Key8 => 39,
+        VirtualKey::Key9 => 40,
+        VirtualKey::Key0 => 41,
+        VirtualKey::A => 42,
+        VirtualKey::B => 43,
+        VirtualKey::C => 44,
+        VirtualKey::D => 45,
+        VirtualKey::E => 46,
+        VirtualKey::F => 47,
+        VirtualKey::G => 48,
+        VirtualKey::H => 49,
+        VirtualKey::I => 50,
+        VirtualKey::J => 51,
+        VirtualKey::K => 52,
+        VirtualKey::L => 53,
+        VirtualKey::M => 54,
+        VirtualKey::N => 55,
+        VirtualKey::O => 56,
+        VirtualKey::P => 57,
+        VirtualKey::Q => 58,
+        VirtualKey::R => 59,
+        VirtualKey::S => 60,
+        VirtualKey::T => 61,
+        VirtualKey::U => 62,
+        VirtualKey::V => 63,
+        VirtualKey::W => 64,
+        VirtualKey::X => 65,
+        VirtualKey::Y => 66,
+        VirtualKey::Z => 67,
+        VirtualKey::Semicolon => 68,
+        VirtualKey::Equal => 69,
+        VirtualKey::Comma => 70,
+        VirtualKey::Minus => 71,
+        VirtualKey::Period => 72,
+        VirtualKey::Slash => 73,
+        VirtualKey::Backquote => 74,
+        VirtualKey::LBracket => 75,
+        VirtualKey::Backslash => 76,
+        VirtualKey::RBracket => 77,
+        VirtualKey::Quote => 78,
+        VirtualKey::F1 => 79,
+        VirtualKey::F2 => 80,
+        VirtualKey::F3 => 81,
+        VirtualKey::F4 => 82,
+        VirtualKey::F5 => 83,
+        VirtualKey::F6 => 84,
+        VirtualKey::F7 => 85,
+        VirtualKey::F8 => 86,
+        VirtualKey::F9 => 87,
+        VirtualKey::F10 => 88,
+        VirtualKey::F11 => 89,
+        VirtualKey::F12 => 90,
+        VirtualKey::F13 => 91,
+        VirtualKey::F14 => 92,
+        VirtualKey::F15 => 93,
+        VirtualKey::F16 => 94,
+        VirtualKey::F17 => 95,
+        VirtualKey::F18 => 96,
+        VirtualKey::F19 => 97,
+        VirtualKey::F20 => 98,
+        VirtualKey::F21 => 99,
+        VirtualKey::F22 => 100,
+        VirtualKey::F23 => 101,
+        VirtualKey::F24 => 102,
+        VirtualKey::NumLock => 103,
+        VirtualKey::Num1 => 104,
+        VirtualKey::Num2 => 105,
+        VirtualKey::Num3 => 106,
+        VirtualKey::Num4 => 107,
+        VirtualKey::Num5 => 108,
+        VirtualKey::Num6 => 109,
+        VirtualKey::Num7 => 110,
+        VirtualKey::Num8 => 111,
+        VirtualKey::Num9 => 112,
+        VirtualKey::Num0 => 113,
+        VirtualKey::NumAdd => 114,
+        VirtualKey::NumSub => 115,
+        VirtualKey::NumMul => 116,
+        VirtualKey::NumDiv => 117,
+        VirtualKey::NumDecimal => 118,
+        VirtualKey::NumEnter => 119,
+        VirtualKey::VolumeMute => 120,
+        VirtualKey::VolumeDown => 121,
+        VirtualKey::VolumeUp => 122,
+        VirtualKey::MediaNextTrack => 123,
+        VirtualKey::MediaPrevTrack => 124,
+        VirtualKey::MediaStop => 125,
+        VirtualKey::MediaPlayPause => 126,
+        VirtualKey::BrowserBack => 127,
+        VirtualKey::BrowserForward => 128,
+        VirtualKey::BrowserRefresh => 129,
+        VirtualKey::BrowserStop => 130,
+        VirtualKey::BrowserSearch => 131,
+        VirtualKey::BrowserFavorites => 132,
+        VirtualKey::BrowserHome => 133,
+        VirtualKey::LaunchMail => 134,
+        VirtualKey::LaunchMediaSelect => 135,
+        VirtualKey::LaunchApp1 => 136,
+        VirtualKey::LaunchApp2 => 137,
+        VirtualKey::IntlBackslash => 138,
+    }
+}
+
+#[inline]
+fn put_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+#[inline]
+fn put_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+#[inline]
+fn put_f64(buf: &mut Vec<u8>, v: f64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+#[inline]
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    put_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+#[inline]
+fn put_position(buf: &mut Vec<u8>, position: &PhysicalPosition<i32>) {
+    buf.extend_from_slice(&position.x.to_le_bytes());
+    buf.extend_from_slice(&position.y.to_le_bytes());
+}
+
+#[inline]
+fn put_button(buf: &mut Vec<u8>, button: MouseButton) {
+    put_u32(buf, MouseButtons::from([button]).bits());
+}
+
+#[inline]
+fn put_state(buf: &mut Vec<u8>, state: KeyState) {
+    buf.push(matches!(state, KeyState::Pressed) as u8);
+}
+
+fn put_event(buf: &mut Vec<u8>, event: &InputEvent) {
+    match event {
+        InputEvent::Key {
+            key,
+            state,
+            repeat,
+            modifiers,
+        } => {
+            buf.push(tag::KEY);
+            put_u32(buf, vkey_tag(key.vkey));
+            put_u32(
+                buf,
+                match key.vkey {
+                    VirtualKey::Other(v) => v,
+                    _ => 0,
+                },
+            );
+            put_u32(buf, key.scan_code.0);
+            put_state(buf, *state);
+            buf.push(*repeat as u8);
+            put_u32(buf, modifiers.bits());
+        }
+        InputEvent::Text(text) => {
+            buf.push(tag::TEXT);
+            match text {
+                TextInputEvent::CompositionStart => buf.push(0),
+                TextInputEvent::Preedit(preedit) => {
+                    buf.push(1);
+                    put_str(buf, &preedit.text);
+                    match preedit.cursor {
+                        Some(cursor) => {
+                            buf.push(1);
+                            put_u32(buf, cursor as u32);
+                        }
+                        None => buf.push(0),
+                    }
+                    put_u32(buf, preedit.clauses.len() as u32);
+                    for clause in &preedit.clauses {
+                        put_u32(buf, clause.range.start as u32);
+                        put_u32(buf, clause.range.end as u32);
+                        buf.push(match clause.state {
+                            ClauseState::Input => 0,
+                            ClauseState::Target => 1,
+                            ClauseState::Converted => 2,
+                        });
+                    }
+                }
+                TextInputEvent::CompositionEnd => buf.push(2),
+                TextInputEvent::Commit(s) => {
+                    buf.push(3);
+                    put_str(buf, s);
+                }
+            }
+        }
+        InputEvent::MouseButton {
+            button,
+            state,
+            position,
+            buttons,
+            modifiers,
+        } => {
+            buf.push(tag::MOUSE_BUTTON);
+            put_button(buf, *button);
+            put_state(buf, *state);
+            put_position(buf, position);
+            put_u32(buf, buttons.bits());
+            put_u32(buf, modifiers.bits());
+        }
+        InputEvent::MouseMove {
+            position,
+            buttons,
+            modifiers,
+        } => {
+            buf.push(tag::MOUSE_MOVE);
+            put_position(buf, position);
+            put_u32(buf, buttons.bits());
+            put_u32(buf, modifiers.bits());
+        }
+        InputEvent::MouseWheel {
+            delta,
+            position,
+            buttons,
+            modifiers,
+        } => {
+            buf.push(tag::MOUSE_WHEEL);
+            buf.push(match delta.axis {
+                MouseWheelAxis::Vertical => 0,
+                MouseWheelAxis::Horizontal => 1,
+            });
+            buf.extend_from_slice(&delta.value.to_le_bytes());
+            buf.push(match delta.unit {
+                WheelUnit::Notch => 0,
+                WheelUnit::Line => 1,
+                WheelUnit::Page => 2,
+                WheelUnit::Pixel => 3,
+            });
+            put_position(buf, position);
+            put_u32(buf, buttons.bits());
+            put_u32(buf, modifiers.bits());
+        }
+        InputEvent::CursorEntered { position } => {
+            buf.push(tag::CURSOR_ENTERED);
+            put_position(buf, position);
+        }
+        InputEvent::CursorLeft => buf.push(tag::CURSOR_LEFT),
+        InputEvent::Touch { point, phase } => {
+            buf.push(tag::TOUCH);
+            put_u64(buf, point.id.0);
+            buf.push(match point.kind {
+                PointerKind::Mouse => 0,
+                PointerKind::Pen => 1,
+                PointerKind::Touch => 2,
+            });
+            buf.push(match phase {
+                TouchPhase::Started => 0,
+                TouchPhase::Moved => 1,
+                TouchPhase::Ended => 2,
+                TouchPhase::Cancelled => 3,
+            });
+            put_f64(buf, point.position.x);
+            put_f64(buf, point.position.y);
+            match point.pressure {
+                Some(pressure) => {
+                    buf.push(1);
+                    buf.extend_from_slice(&pressure.to_le_bytes());
+                }
+                None => buf.push(0),
+            }
+            match point.contact_size {
+                Some(size) => {
+                    buf.push(1);
+                    put_f64(buf, size.width);
+                    put_f64(buf, size.height);
+                }
+                None => buf.push(0),
+            }
+        }
+        InputEvent::Focused => buf.push(tag::FOCUSED),
+        InputEvent::Unfocused => buf.push(tag::UNFOCUSED),
+    }
+}
+
+struct Source<'a>(&'a [u8]);
+
+impl<'a> Source<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], RecordingError> {
+        if self.0.len() < len {
+            return Err(RecordingError::InvalidData("unexpected end"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    #[inline]
+    fn u8(&mut self) -> Result<u8, RecordingError> {
+        Ok(self.take(1)?[0])
+    }
+
+    #[inline]
+    fn u32(&mut self) -> Result<u32, RecordingError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> Result<u64, RecordingError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    #[inline]
+    fn f32(&mut self) -> Result<f32, RecordingError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    #[inline]
+    fn f64(&mut self) -> Result<f64, RecordingError> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, RecordingError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| RecordingError::InvalidData("string"))
+    }
+
+    fn position(&mut self) -> Result<PhysicalPosition<i32>, RecordingError> {
+        let x = i32::from_le_bytes(self.take(4)?.try_into().unwrap());
+        let y = i32::from_le_bytes(self.take(4)?.try_into().unwrap());
+        Ok(PhysicalPosition::new(x, y))
+    }
+
+    fn button(&mut self) -> Result<MouseButton, RecordingError> {
+        let mut buttons = MouseButtons::from_bits(self.u32()?).into_iter();
+        match (buttons.next(), buttons.next()) {
+            (Some(button), None) => Ok(button),
+            _ => Err(RecordingError::InvalidData("mouse button")),
+        }
+    }
+
+    fn vkey(&mut self) -> Result<VirtualKey, RecordingError> {
+        let tag = self.u32()?;
+        let value = self.u32()?;
+        if tag == 0 {
+            return Ok(VirtualKey::Other(value));
+        }
+        VirtualKey::ALL
+            .into_iter()
+            .find(|vkey| vkey_tag(*vkey) == tag)
+            .ok_or(RecordingError::InvalidData("virtual key"))
+    }
+
+    fn state(&mut self) -> Result<KeyState, RecordingError> {
+        match self.u8()? {
+            0 => Ok(KeyState::Released),
+            1 => Ok(KeyState::Pressed),
+            _ => Err(RecordingError::InvalidData("key state")),
+        }
+    }
+
+    fn text(&mut self) -> Result<TextInputEvent, RecordingError> {
+        let event = match self.u8()? {
+            0 => TextInputEvent::CompositionStart,
+            1 => {
+                let text = self.string()?;
+                let cursor = match self.u8()? {
+                    0 => None,
+                    _ => Some(self.u32()? as usize),
+                };
+                let len = self.u32()?;
+                let mut clauses = vec![];
+                for _ in 0..len {
+                    let start = self.u32()? as usize;
+                    let end = self.u32()? as usize;
+                    let state = match self.u8()? {
+                        0 => ClauseState::Input,
+                        1 => ClauseState::Target,
+                        2 => ClauseState::Converted,
+                        _ => return Err(RecordingError::InvalidData("clause state")),
+                    };
+                    clauses.push(Clause::new(start..end, state));
+                }
+                TextInputEvent::Preedit(Preedit {
+                    text,
+                    cursor,
+                    clauses,
+                })
+            }
+            2 => TextInputEvent::CompositionEnd,
+            3 => TextInputEvent::Commit(self.string()?),
+            _ => return Err(RecordingError::InvalidData("text input event")),
+        };
+        Ok(event)
+    }
+
+    fn event(&mut self) -> Result<InputEvent, RecordingError> {
+        let event = match self.u8()? {
+            tag::KEY => {
+                let vkey = self.vkey()?;
+                let scan_code = ScanCode(self.u32()?);
+                InputEvent::Key {
+                    key: KeyCode::new(vkey, scan_code),
+                    state: self.state()?,
+                    repeat: self.u8()? != 0,
+                    modifiers: Modifiers::from_bits(self.u32()?),
+                }
+            }
+            tag::TEXT => InputEvent::Text(self.text()?),
+            tag::MOUSE_BUTTON => InputEvent::MouseButton {
+                button: self.button()?,
+                state: self.state()?,
+                position: self.position()?,
+                buttons: MouseButtons::from_bits(self.u32()?),
+                modifiers: Modifiers::from_bits(self.u32()?),
+            },
+            tag::MOUSE_MOVE => InputEvent::MouseMove {
+                position: self.position()?,
+                buttons: MouseButtons::from_bits(self.u32()?),
+                modifiers: Modifiers::from_bits(self.u32()?),
+            },
+            tag::MOUSE_WHEEL => {
+                let axis = match self.u8()? {
+                    0 => MouseWheelAxis::Vertical,
+                    1 => MouseWheelAxis::Horizontal,
+                    _ => return Err(RecordingError::InvalidData("wheel axis")),
+                };
+                let value = self.f32()?;
+                let unit = match self.u8()? {
+                    0 => WheelUnit::Notch,
+                    1 => WheelUnit::Line,
+                    2 => WheelUnit::Page,
+                    3 => WheelUnit::Pixel,
+                    _ => return Err(RecordingError::InvalidData("wheel unit")),
+                };
+                InputEvent::MouseWheel {
+                    delta: WheelDelta::new(axis, value, unit),
+                    position: self.position()?,
+                    buttons: MouseButtons::from_bits(self.u32()?),
+                    modifiers: Modifiers::from_bits(self.u32()?),
+                }
+            }
+            tag::CURSOR_ENTERED => InputEvent::CursorEntered {
+                position: self.position()?,
+            },
+            tag::CURSOR_LEFT => InputEvent::CursorLeft,
+            tag::TOUCH => {
+                let id = PointerId(self.u64()?);
+                let kind = match self.u8()? {
+                    0 => PointerKind::Mouse,
+                    1 => PointerKind::Pen,
+                    2 => PointerKind::Touch,
+                    _ => return Err(RecordingError::InvalidData("pointer kind")),
+                };
+                let phase = match self.u8()? {
+                    0 => TouchPhase::Started,
+                    1 => TouchPhase::Moved,
+                    2 => TouchPhase::Ended,
+                    3 => TouchPhase::Cancelled,
+                    _ => return Err(RecordingError::InvalidData("touch phase")),
+                };
+                let position = PhysicalPosition::new(self.f64()?, self.f64()?);
+                let pressure = match self.u8()? {
+                    0 => None,
+                    _ => Some(self.f32()?),
+                };
+                let contact_size = match self.u8()? {
+                    0 => None,
+                    _ => Some(PhysicalSize::new(self.f64()?, self.f64()?)),
+                };
+                InputEvent::Touch {
+                    point: TouchPoint {
+                        id,
+                        kind,
+                        position,
+                        pressure,
+                        contact_size,
+                    },
+                    phase,
+                }
+            }
+            tag::FOCUSED => InputEvent::Focused,
+            tag::UNFOCUSED => InputEvent::Unfocused,
+            _ => return Err(RecordingError::InvalidData("event tag")),
+        };
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Recording {
+        let start = Instant::now();
+        let mut recorder = Recorder::with_start(start);
+        let events = [
+            InputEvent::Focused,
+            InputEvent::Key {
+                key: KeyCode::new(VirtualKey::Other(0x1234), ScanCode(0xe01d)),
+                state: KeyState::Pressed,
+                repeat: false,
+                modifiers: Modifier::RCtrl | Modifier::CapsLock,
+            },
+            InputEvent::Key {
+                key: KeyCode::new(VirtualKey::LaunchApp2, ScanCode(0)),
+                state: KeyState::Released,
+                repeat: false,
+                modifiers: Modifiers::new(),
+            },
+            InputEvent::Text(TextInputEvent::Preedit(
+                Preedit::new("にほん", Some(3))
+                    .with_clauses([Clause::new(0..9, ClauseState::Target)]),
+            )),
+            InputEvent::Text(TextInputEvent::Commit("日本".to_string())),
+            InputEvent::MouseButton {
+                button: MouseButton::Ex(1),
+                state: ButtonState::Pressed,
+                position: PhysicalPosition::new(-5, 7),
+                buttons: MouseButton::Ex(1) | MouseButton::Left,
+                modifiers: Modifier::LShift.into(),
+            },
+            InputEvent::MouseMove {
+                position: PhysicalPosition::new(1, 2),
+                buttons: MouseButtons::new(),
+                modifiers: Modifiers::new(),
+            },
+            InputEvent::MouseWheel {
+                delta: WheelDelta::new(MouseWheelAxis::Horizontal, 0.25, WheelUnit::Pixel),
+                position: PhysicalPosition::new(3, 4),
+                buttons: MouseButtons::new(),
+                modifiers: Modifiers::new(),
+            },
+            InputEvent::CursorEntered {
+                position: PhysicalPosition::new(0, 0),
+            },
+            InputEvent::CursorLeft,
+            InputEvent::Touch {
+                point: TouchPoint::new(PointerId(u64::MAX), PointerKind::Pen, (9.5, -9.0)),
+                phase: TouchPhase::Cancelled,
+            },
+            InputEvent::Touch {
+                point: TouchPoint {
+                    pressure: Some(0.75),
+                    contact_size: Some(PhysicalSize::new(3.0, 4.0)),
+                    ..TouchPoint::new(PointerId(1), PointerKind::Touch, (1.0, 2.0))
+                },
+                phase: TouchPhase::Started,
+            },
+            InputEvent::Unfocused,
+        ];
+        for (i, event) in events.into_iter().enumerate() {
+            recorder.record_at(start + Duration::from_millis(i as u64 * 10), event);
+        }
+        recorder.finish()
+    }
+
+    /// `InputEvent` equality ignores scan codes, which recordings must keep as well.
+    fn scan_codes(recording: &Recording) -> Vec<ScanCode> {
+        recording
+            .events
+            .iter()
+            .filter_map(|e| match &e.event {
+                InputEvent::Key { key, .. } => Some(key.scan_code),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recording_json_lines() {
+        let recording = sample();
+        let mut buf = vec![];
+        recording.write_json_lines(&mut buf).unwrap();
+        let text = String::from_utf8(buf.clone()).unwrap();
+        assert!(text.starts_with(r#"{"format":"gedv-recording","version":1}"#));
+        assert!(text.lines().count() == recording.events.len() + 1);
+        let read = Recording::read_json_lines(buf.as_slice()).unwrap();
+        assert!(read == recording);
+        assert!(scan_codes(&read) == scan_codes(&recording));
+        let bad = br#"{"format":"gedv-recording","version":99}"#;
+        assert!(matches!(
+            Recording::read_json_lines(bad.as_slice()),
+            Err(RecordingError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    fn recording_binary() {
+        let recording = sample();
+        let mut buf = vec![];
+        recording.write_binary(&mut buf).unwrap();
+        assert!(buf.starts_with(b"GEDV"));
+        let read = Recording::read_binary(buf.as_slice()).unwrap();
+        assert!(read == recording);
+        assert!(scan_codes(&read) == scan_codes(&recording));
+        assert!(matches!(
+            Recording::read_binary(&buf[..buf.len() - 1]),
+            Err(RecordingError::InvalidData(_))
+        ));
+        assert!(matches!(
+            Recording::read_binary(b"JSON".as_slice()),
+            Err(RecordingError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn recording_malformed() {
+        let mut buf = vec![];
+        sample().write_json_lines(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.contains(r#""button":"X2""#));
+        for button in [r#"{"Ex":40}"#, r#""X40""#, r#"{"Ex":4294967295}"#] {
+            let bad = text.replace(r#""button":"X2""#, &format!(r#""button":{}"#, button));
+            assert!(matches!(
+                Recording::read_json_lines(bad.as_bytes()),
+                Err(RecordingError::Json(_))
+            ));
+        }
+        let mut recording = Recording::new();
+        recording.events.push(RecordedEvent {
+            time: Duration::ZERO,
+            event: InputEvent::Key {
+                key: KeyCode::new(VirtualKey::A, ScanCode(0x1e)),
+                state: KeyState::Pressed,
+                repeat: false,
+                modifiers: Modifiers::new(),
+            },
+        });
+        let mut buf = vec![];
+        recording.write_binary(&mut buf).unwrap();
+        // Header, time and event tag come before the virtual key tag.
+        assert!(buf[17..21] == vkey_tag(VirtualKey::A).to_le_bytes());
+        buf[17..21].copy_from_slice(&999u32.to_le_bytes());
+        assert!(matches!(
+            Recording::read_binary(buf.as_slice()),
+            Err(RecordingError::InvalidData("virtual key"))
+        ));
+    }
+
+    #[test]
+    fn player_timing() {
+        let recording = sample();
+        let len = recording.events.len();
+        let mut player = Player::new(recording.clone(), 2.0);
+        let start = Instant::now();
+        assert!(player.time_until_next(start) == Some(Duration::ZERO));
+        assert!(player.poll(start) == Some(InputEvent::Focused));
+        assert!(player.poll(start).is_none());
+        assert!(player.time_until_next(start) == Some(Duration::from_millis(5)));
+        assert!(player.poll(start + Duration::from_millis(5)).is_some());
+        assert!(player.poll(start + Duration::from_secs(1)).is_some());
+        let player = Player::new(recording, f64::INFINITY);
+        assert!(player.count() == len);
+    }
+}
@@ -0,0 +1,420 @@
+//! Synthetic input for headless tests.
+
+use super::*;
+
+/// Builds a sequence of `InputEvent`s.
+///
+/// Keys are typed through a `KeyboardLayout`, US by default, and each typed character also
+/// produces a `TextInputEvent::Commit`. Mouse events start at `(0, 0)`.
+#[derive(Clone, Debug)]
+pub struct InputBuilder {
+    layout: KeyboardLayout,
+    events: Vec<InputEvent>,
+    modifiers: Modifiers,
+    position: PhysicalPosition<i32>,
+    buttons: MouseButtons,
+}
+
+impl InputBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_layout(KeyboardLayout::US)
+    }
+
+    #[inline]
+    pub fn with_layout(layout: KeyboardLayout) -> Self {
+        Self {
+            layout,
+            events: vec![],
+            modifiers: Modifiers::new(),
+            position: PhysicalPosition::new(0, 0),
+            buttons: MouseButtons::new(),
+        }
+    }
+
+    /// `'\n'`, `'\t'` and `'\u{8}'` press Enter, Tab and BackSpace without committing text.
+    ///
+    /// Panics if the layout cannot type a character of `text`.
+    pub fn type_text(mut self, text: &str) -> Self {
+        let mut composer = DeadKeyComposer::new();
+        for c in text.chars() {
+            let control = match c {
+                '\n' => Some(VirtualKey::Enter),
+                '\t' => Some(VirtualKey::Tab),
+                '\u{8}' => Some(VirtualKey::BackSpace),
+                _ => None,
+            };
+            if let Some(vkey) = control {
+                let key = vkey_key(vkey);
+                self.key(key, KeyState::Pressed);
+                self.key(key, KeyState::Released);
+                continue;
+            }
+            let strokes = self
+                .layout
+                .keystrokes(c)
+                .unwrap_or_else(|| panic!("{} layout cannot type {:?}", self.layout.name(), c));
+            for (scan_code, modifiers) in strokes {
+                let key = self.layout_key(scan_code);
+                self.hold(modifiers);
+                self.key(key, KeyState::Pressed);
+                if let Some(text) = self.layout.text(scan_code, self.modifiers, &mut composer) {
+                    self.events
+                        .push(InputEvent::Text(TextInputEvent::Commit(text)));
+                }
+                self.key(key, KeyState::Released);
+                self.release(modifiers);
+            }
+        }
+        self
+    }
+
+    /// Presses and releases `shortcut` with the left modifier keys.
+    pub fn press(mut self, shortcut: Shortcut) -> Self {
+        let modifiers = [
+            (Modifier::Ctrl, Modifier::LCtrl),
+            (Modifier::Alt, Modifier::LAlt),
+            (Modifier::Shift, Modifier::LShift),
+            (Modifier::Meta, Modifier::LMeta),
+        ]
+        .into_iter()
+        .filter(|(m, _)| shortcut.modifiers.contains(*m))
+        .map(|(_, side)| side)
+        .collect();
+        let key = vkey_key(shortcut.key);
+        self.hold(modifiers);
+        self.key(key, KeyState::Pressed);
+        self.key(key, KeyState::Released);
+        self.release(modifiers);
+        self
+    }
+
+    pub fn key_down(mut self, vkey: VirtualKey) -> Self {
+        self.key(vkey_key(vkey), KeyState::Pressed);
+        self
+    }
+
+    pub fn key_up(mut self, vkey: VirtualKey) -> Self {
+        self.key(vkey_key(vkey), KeyState::Released);
+        self
+    }
+
+    pub fn move_to(mut self, position: impl Into<PhysicalPosition<i32>>) -> Self {
+        self.mouse_move(position.into());
+        self
+    }
+
+    pub fn click(
+        mut self,
+        button: MouseButton,
+        position: impl Into<PhysicalPosition<i32>>,
+    ) -> Self {
+        self.mouse_move(position.into());
+        self.button(button, ButtonState::Pressed);
+        self.button(button, ButtonState::Released);
+        self
+    }
+
+    /// Drags with the left button, moving in `steps` equal moves.
+    pub fn drag(
+        mut self,
+        from: impl Into<PhysicalPosition<i32>>,
+        to: impl Into<PhysicalPosition<i32>>,
+        steps: u32,
+    ) -> Self {
+        let from = from.into();
+        let to = to.into();
+        let steps = steps.max(1) as i32;
+        self.mouse_move(from);
+        self.button(MouseButton::Left, ButtonState::Pressed);
+        for i in 1..=steps {
+            let x = from.x + (to.x - from.x) * i / steps;
+            let y = from.y + (to.y - from.y) * i / steps;
+            self.mouse_move(PhysicalPosition::new(x, y));
+        }
+        self.button(MouseButton::Left, ButtonState::Released);
+        self
+    }
+
+    /// Scrolls one notch per event. Positive `notches` scroll up or right.
+    pub fn scroll(mut self, axis: MouseWheelAxis, notches: i32) -> Self {
+        let raw = WheelDelta::NOTCH * notches.signum();
+        for _ in 0..notches.unsigned_abs() {
+            self.events.push(InputEvent::MouseWheel {
+                delta: WheelDelta::from_raw(axis, raw),
+                position: self.position,
+                buttons: self.buttons,
+                modifiers: self.modifiers,
+            });
+        }
+        self
+    }
+
+    pub fn event(mut self, event: InputEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Vec<InputEvent> {
+        self.events
+    }
+
+    fn key(&mut self, key: KeyCode, state: KeyState) {
+        if let Some(m) = Modifier::from_vkey(key.vkey) {
+            match state {
+                KeyState::Pressed => self.modifiers.insert(m),
+                KeyState::Released => self.modifiers.remove(m),
+            }
+        }
+        self.events.push(InputEvent::Key {
+            key,
+            state,
+            repeat: false,
+            modifiers: self.modifiers,
+        });
+    }
+
+    fn hold(&mut self, modifiers: Modifiers) {
+        for m in modifiers {
+            if let Some(vkey) = modifier_vkey(m) {
+                self.key(vkey_key(vkey), KeyState::Pressed);
+            }
+        }
+    }
+
+    fn release(&mut self, modifiers: Modifiers) {
+        for m in modifiers.to_vec().into_iter().rev() {
+            if let Some(vkey) = modifier_vkey(m) {
+                self.key(vkey_key(vkey), KeyState::Released);
+            }
+        }
+    }
+
+    fn mouse_move(&mut self, position: PhysicalPosition<i32>) {
+        if position == self.position {
+            return;
+        }
+        self.position = position;
+        self.events.push(InputEvent::MouseMove {
+            position,
+            buttons: self.buttons,
+            modifiers: self.modifiers,
+        });
+    }
+
+    fn button(&mut self, button: MouseButton, state: ButtonState) {
+        match state {
+            ButtonState::Pressed => self.buttons.insert(button),
+            ButtonState::Released => self.buttons.remove(button),
+        }
+        self.events.push(InputEvent::MouseButton {
+            button,
+            state,
+            position: self.position,
+            buttons: self.buttons,
+            modifiers: self.modifiers,
+        });
+    }
+
+    /// The virtual key follows the character on the key for letters and digits, as the OS does.
+    fn layout_key(&self, scan_code: ScanCode) -> KeyCode {
+        let vkey = match self.layout.symbol(scan_code, Modifiers::new()) {
+            Some(KeySymbol::Char(c)) if c.is_ascii_alphanumeric() => c.to_string().parse().ok(),
+            _ => None,
+        };
+        let vkey = vkey
+            .or_else(|| {
+                scan_code
+                    .to_evdev()
+                    .map(|code| KeyCode::from_evdev(code).vkey)
+            })
+            .unwrap_or(VirtualKey::Other(0));
+        KeyCode::new(vkey, scan_code)
+    }
+}
+
+impl Default for InputBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn vkey_key(vkey: VirtualKey) -> KeyCode {
+    let scan_code = vkey
+        .to_evdev()
+        .and_then(ScanCode::from_evdev)
+        .unwrap_or(ScanCode(0));
+    KeyCode::new(vkey, scan_code)
+}
+
+fn modifier_vkey(modifier: Modifier) -> Option<VirtualKey> {
+    let vkey = match modifier {
+        Modifier::Shift | Modifier::LShift => VirtualKey::LShift,
+        Modifier::RShift => VirtualKey::RShift,
+        Modifier::Ctrl | Modifier::LCtrl => VirtualKey::LCtrl,
+        Modifier::RCtrl => VirtualKey::RCtrl,
+        Modifier::Alt | Modifier::LAlt => VirtualKey::LAlt,
+        Modifier::RAlt => VirtualKey::RAlt,
+        Modifier::Meta | Modifier::LMeta => VirtualKey::LMeta,
+        Modifier::RMeta => VirtualKey::RMeta,
+        Modifier::CapsLock | Modifier::NumLock => return None,
+    };
+    Some(vkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(events: &[InputEvent]) -> Vec<(VirtualKey, KeyState)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                InputEvent::Key { key, state, .. } => Some((key.vkey, *state)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn text(events: &[InputEvent]) -> String {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                InputEvent::Text(TextInputEvent::Commit(s)) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn type_text() {
+        let events = InputBuilder::new().type_text("Hi!").build();
+        assert!(text(&events) == "Hi!");
+        assert!(
+            keys(&events)
+                == [
+                    (VirtualKey::LShift, KeyState::Pressed),
+                    (VirtualKey::H, KeyState::Pressed),
+                    (VirtualKey::H, KeyState::Released),
+                    (VirtualKey::LShift, KeyState::Released),
+                    (VirtualKey::I, KeyState::Pressed),
+                    (VirtualKey::I, KeyState::Released),
+                    (VirtualKey::LShift, KeyState::Pressed),
+                    (VirtualKey::Key1, KeyState::Pressed),
+                    (VirtualKey::Key1, KeyState::Released),
+                    (VirtualKey::LShift, KeyState::Released),
+                ]
+        );
+        let InputEvent::Key { key, modifiers, .. } = events[1] else {
+            panic!();
+        };
+        assert!(key.scan_code == ScanCode(0x23));
+        assert!(modifiers == Modifiers::from(Modifier::LShift));
+        let events = InputBuilder::with_layout(KeyboardLayout::DE)
+            .type_text("zê")
+            .build();
+        assert!(text(&events) == "zê");
+        assert!(keys(&events)[0] == (VirtualKey::Z, KeyState::Pressed));
+        let InputEvent::Key { key, .. } = events[0] else {
+            panic!();
+        };
+        assert!(key.scan_code == ScanCode(0x15));
+    }
+
+    #[test]
+    fn type_control_and_accents() {
+        let events = InputBuilder::new().type_text("a\n\tb\u{8}").build();
+        assert!(text(&events) == "ab");
+        assert!(
+            keys(&events)
+                == [
+                    (VirtualKey::A, KeyState::Pressed),
+                    (VirtualKey::A, KeyState::Released),
+                    (VirtualKey::Enter, KeyState::Pressed),
+                    (VirtualKey::Enter, KeyState::Released),
+                    (VirtualKey::Tab, KeyState::Pressed),
+                    (VirtualKey::Tab, KeyState::Released),
+                    (VirtualKey::B, KeyState::Pressed),
+                    (VirtualKey::B, KeyState::Released),
+                    (VirtualKey::BackSpace, KeyState::Pressed),
+                    (VirtualKey::BackSpace, KeyState::Released),
+                ]
+        );
+        let events = InputBuilder::with_layout(KeyboardLayout::DE)
+            .type_text("^a")
+            .build();
+        assert!(text(&events) == "^a");
+        assert!(keys(&events)[2] == (VirtualKey::Space, KeyState::Pressed));
+    }
+
+    #[test]
+    fn press_shortcut() {
+        let events = InputBuilder::new()
+            .press("Ctrl+Shift+S".parse().unwrap())
+            .build();
+        assert!(
+            keys(&events)
+                == [
+                    (VirtualKey::LCtrl, KeyState::Pressed),
+                    (VirtualKey::LShift, KeyState::Pressed),
+                    (VirtualKey::S, KeyState::Pressed),
+                    (VirtualKey::S, KeyState::Released),
+                    (VirtualKey::LShift, KeyState::Released),
+                    (VirtualKey::LCtrl, KeyState::Released),
+                ]
+        );
+        let shortcut: Shortcut = "Ctrl+Shift+S".parse().unwrap();
+        let InputEvent::Key { key, modifiers, .. } = events[2] else {
+            panic!();
+        };
+        assert!(shortcut.matches(key, modifiers));
+        assert!(text(&events).is_empty());
+    }
+
+    #[test]
+    fn mouse_input() {
+        let events = InputBuilder::new()
+            .click(MouseButton::Left, (10, 20))
+            .drag((0, 0), (10, 5), 2)
+            .scroll(MouseWheelAxis::Vertical, -2)
+            .build();
+        let mut state = MouseState::default();
+        let now = std::time::Instant::now();
+        let mut clicks = 0;
+        let mut drag_end = None;
+        for event in &events {
+            let result = match *event {
+                InputEvent::MouseButton {
+                    button,
+                    state: button_state,
+                    position,
+                    ..
+                } => state.input_button(button, button_state, position, now),
+                InputEvent::MouseMove { position, .. } => state.input_move(position),
+                _ => None,
+            };
+            match result {
+                Some(MouseEvent::Click { .. }) => clicks += 1,
+                Some(MouseEvent::DragEnd { position, .. }) => drag_end = Some(position),
+                _ => {}
+            }
+        }
+        assert!(clicks == 2);
+        assert!(drag_end == Some(PhysicalPosition::new(10, 5)));
+        let wheels = events
+            .iter()
+            .filter(|e| matches!(e, InputEvent::MouseWheel { delta, .. } if delta.value == -1.0))
+            .count();
+        assert!(wheels == 2);
+        let events = InputBuilder::new()
+            .key_down(VirtualKey::LCtrl)
+            .click(MouseButton::Left, (1, 1))
+            .build();
+        assert!(matches!(
+            events.last(),
+            Some(InputEvent::MouseButton { modifiers, .. }) if *modifiers == Modifier::LCtrl.into()
+        ));
+    }
+}
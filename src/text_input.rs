@@ -0,0 +1,136 @@
+use super::*;
+use std::ops::Range;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClauseState {
+    /// Not converted yet.
+    Input,
+    /// Being converted, where the candidate window applies.
+    Target,
+    Converted,
+}
+
+impl std::fmt::Display for ClauseState {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A segment of a `Preedit`. `range` is in bytes of `Preedit::text`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clause {
+    pub range: Range<usize>,
+    pub state: ClauseState,
+}
+
+impl Clause {
+    #[inline]
+    pub fn new(range: Range<usize>, state: ClauseState) -> Self {
+        Self { range, state }
+    }
+}
+
+/// The text being composed by an IME. `cursor` is a byte offset into `text`.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Preedit {
+    pub text: String,
+    pub cursor: Option<usize>,
+    pub clauses: Vec<Clause>,
+}
+
+impl Preedit {
+    #[inline]
+    pub fn new(text: impl Into<String>, cursor: Option<usize>) -> Self {
+        Self {
+            text: text.into(),
+            cursor,
+            clauses: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn with_clauses(mut self, clauses: impl Into<Vec<Clause>>) -> Self {
+        self.clauses = clauses.into();
+        self
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Returns `None` if the range of `clause` is not on char boundaries of `text`.
+    #[inline]
+    pub fn clause_text(&self, clause: &Clause) -> Option<&str> {
+        self.text.get(clause.range.clone())
+    }
+
+    #[inline]
+    pub fn target_clause(&self) -> Option<&Clause> {
+        self.clauses.iter().find(|c| c.state == ClauseState::Target)
+    }
+}
+
+/// Where the IME places its candidate window, usually the caret or the target clause.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandidateAnchor {
+    pub rect: LogicalRect<f32>,
+}
+
+impl CandidateAnchor {
+    #[inline]
+    pub fn new(rect: LogicalRect<f32>) -> Self {
+        Self { rect }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextInputEvent {
+    CompositionStart,
+    /// The preedit replaces the previous one. An empty preedit clears it.
+    Preedit(Preedit),
+    CompositionEnd,
+    /// Text to insert, from the IME or directly from key input.
+    Commit(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preedit_clauses() {
+        let preedit = Preedit::new("にほんご", Some(6)).with_clauses([
+            Clause::new(0..6, ClauseState::Target),
+            Clause::new(6..12, ClauseState::Input),
+        ]);
+        let target = preedit.target_clause().unwrap();
+        assert!(preedit.clause_text(target) == Some("にほ"));
+        assert!(
+            preedit
+                .clause_text(&Clause::new(1..3, ClauseState::Input))
+                .is_none()
+        );
+        assert!(!preedit.is_empty());
+        assert!(Preedit::default().target_clause().is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn text_input_event_serde() {
+        let event = TextInputEvent::Preedit(
+            Preedit::new("abc", Some(1)).with_clauses([Clause::new(0..3, ClauseState::Input)]),
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(serde_json::from_str::<TextInputEvent>(&json).unwrap() == event);
+        let anchor = CandidateAnchor::new(LogicalRect::new(1.0, 2.0, 3.0, 4.0));
+        let json = serde_json::to_string(&anchor).unwrap();
+        assert!(serde_json::from_str::<CandidateAnchor>(&json).unwrap() == anchor);
+    }
+}